@@ -0,0 +1,132 @@
+// Puts the items of all files of a crate into one graph. There is an edge from an item to every item whose name it mentions, and everything that cannot be reached from a root is dead code.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+use crate::source::{Item, ItemKind, Summary};
+
+pub struct Node {
+    // The path of the file, relative to the crate.
+    pub file: String,
+    pub item: Item,
+}
+
+impl Node {
+    pub fn describe(&self) -> String {
+        let kind = match self.item.kind {
+            ItemKind::Fn => "fn",
+            ItemKind::Const => "const",
+            ItemKind::Static => "static",
+        };
+        format!("{kind} {}", self.item.name)
+    }
+
+    fn id(&self, crate_name: &str) -> String {
+        format!("\"{crate_name}/{}:{}:{}\"", self.file, self.item.line, self.item.name).replace('\\', "/")
+    }
+}
+
+pub struct Graph {
+    pub name: String,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<(usize, usize)>,
+    pub reachable: Vec<bool>,
+}
+
+impl Graph {
+    pub fn new(name: &str, files: Vec<(String, Summary)>) -> Graph {
+        let mut nodes = Vec::new();
+        let mut loose_uses = HashSet::new();
+        for (file, summary) in files {
+            loose_uses.extend(summary.loose_uses);
+            nodes.extend(summary.items.into_iter().map(|item| Node { file: file.clone(), item }));
+        }
+
+        // Items with the same name, like the many fmt and new functions, share one entry, and a use of the name reaches all of them.
+        let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            by_name.entry(node.item.name.as_str()).or_default().push(i);
+        }
+
+        let mut edges = Vec::new();
+        for (from, node) in nodes.iter().enumerate() {
+            let uses: HashSet<&str> = node.item.uses.iter().map(String::as_str).collect();
+            let mut targets: Vec<usize> = uses.iter().filter_map(|name| by_name.get(name)).flatten().copied().filter(|&to| to != from).collect();
+            targets.sort();
+            edges.extend(targets.into_iter().map(|to| (from, to)));
+        }
+
+        // Breadth-first search from the roots. Every item seen on the way is reachable.
+        let mut reachable = vec![false; nodes.len()];
+        let mut queue: VecDeque<usize> = nodes.iter().enumerate().filter(|(_, node)| node.item.root || loose_uses.contains(&node.item.name)).map(|(i, _)| i).collect();
+        while let Some(i) = queue.pop_front() {
+            if reachable[i] {
+                continue;
+            }
+            reachable[i] = true;
+            queue.extend(edges.iter().filter(|&&(from, _)| from == i).map(|&(_, to)| to));
+        }
+
+        Graph { name: name.to_string(), nodes, edges, reachable }
+    }
+
+    pub fn unreachable(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter().zip(&self.reachable).filter(|(_, reachable)| !**reachable).map(|(node, _)| node)
+    }
+
+    // Writes the crate as a cluster of a Graphviz graph. Dead code is drawn dashed and grey, and roots with a double border.
+    pub fn dot(&self, out: &mut String) {
+        let _ = writeln!(out, "    subgraph \"cluster_{}\" {{", self.name);
+        let _ = writeln!(out, "        label = \"{}\";", self.name);
+        for (node, reachable) in self.nodes.iter().zip(&self.reachable) {
+            let style = match (node.item.root, reachable) {
+                (true, _) => ", peripheries=2",
+                (false, true) => "",
+                (false, false) => ", style=dashed, color=gray, fontcolor=gray",
+            };
+            let _ = writeln!(out, "        {} [label=\"{}\"{style}];", node.id(&self.name), node.item.name);
+        }
+        let _ = writeln!(out, "    }}");
+        for &(from, to) in &self.edges {
+            let _ = writeln!(out, "    {} -> {};", self.nodes[from].id(&self.name), self.nodes[to].id(&self.name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::analyze;
+    use crate::tokenizer::tokenize;
+
+    fn graph(files: &[(&str, &str)]) -> Graph {
+        Graph::new("lesson", files.iter().map(|(file, source)| (file.to_string(), analyze(&tokenize(source), false))).collect())
+    }
+
+    #[test]
+    fn finds_what_main_cannot_reach() {
+        let graph = graph(&[
+            ("src/main.rs", "fn main() { helper::run(); } fn call_five() { five(); } fn five() -> i32 { 5 }"),
+            ("src/helper.rs", "pub fn run() { plus_one(1); } fn plus_one(x: i32) -> i32 { x + 1 } fn unused() { unused() }"),
+        ]);
+        let dead: Vec<String> = graph.unreachable().map(Node::describe).collect();
+        assert_eq!(dead, ["fn call_five", "fn five", "fn unused"]);
+        assert_eq!(graph.edges.len(), 3);
+    }
+
+    #[test]
+    fn names_used_in_macros_are_reachable() {
+        let graph = graph(&[("src/lib.rs", "macro_rules! m { () => { helper() } } fn helper() {}")]);
+        assert_eq!(graph.unreachable().count(), 0);
+    }
+
+    #[test]
+    fn writes_graphviz() {
+        let mut out = String::new();
+        graph(&[("src/main.rs", "fn main() { five(); } fn five() -> i32 { 5 } fn six() {}")]).dot(&mut out);
+        assert!(out.contains("subgraph \"cluster_lesson\""));
+        assert!(out.contains("\"lesson/src/main.rs:1:main\" [label=\"main\", peripheries=2];"));
+        assert!(out.contains("\"lesson/src/main.rs:1:main\" -> \"lesson/src/main.rs:1:five\";"));
+        assert!(out.contains("[label=\"six\", style=dashed"));
+    }
+}
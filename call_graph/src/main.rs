@@ -0,0 +1,147 @@
+// Reads the sources of the lessons and reports the code nothing uses: functions that main never reaches, directly or through other functions, and variables that are bound but never read. With --dot it prints the call graph for Graphviz instead.
+//
+//   cargo run                          reports on every lesson in the repository
+//   cargo run -- ../functions          reports on one lesson
+//   cargo run -- --dot | dot -Tsvg > calls.svg
+//
+// It does its own tokenizing and parsing instead of asking the compiler, so it only understands the shape of fn, const, let and calls. src/source.rs lists what that means for the results.
+
+mod graph;
+mod source;
+mod tokenizer;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use graph::Graph;
+use source::Binding;
+
+const USAGE: &str = "usage: call_graph [--dot] [DIR...]";
+
+struct Report {
+    graph: Graph,
+    unused: Vec<(String, Binding)>,
+}
+
+// Every .rs file below dir, sorted so the output is always in the same order.
+fn sources(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            sources(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(())
+}
+
+// The modules src/main.rs declares with mod name;. They belong to the binary even if the package also has a library.
+fn binary_modules(src: &Path) -> Vec<String> {
+    let Ok(main) = fs::read_to_string(src.join("main.rs")) else { return Vec::new() };
+    let tokens = tokenizer::tokenize(&main);
+    tokens.windows(3).filter(|window| window[0].is("mod") && window[2].is(";")).map(|window| window[1].text.clone()).collect()
+}
+
+fn analyze_crate(dir: &Path) -> io::Result<Report> {
+    let name = dir.canonicalize()?.file_name().map_or_else(|| "crate".to_string(), |name| name.to_string_lossy().into_owned());
+    let src = dir.join("src");
+    let mut files = Vec::new();
+    sources(&src, &mut files)?;
+
+    // In a package with src/lib.rs, pub items of the library can be used by other crates, so they are not dead even if this crate does not call them.
+    let has_library = src.join("lib.rs").is_file();
+    let binary = binary_modules(&src);
+
+    let mut summaries = Vec::new();
+    let mut unused = Vec::new();
+    for path in files {
+        let relative = path.strip_prefix(dir).unwrap_or(&path).to_string_lossy().into_owned();
+        let module = path.strip_prefix(&src).ok().and_then(|rest| rest.components().next()).map(|first| first.as_os_str().to_string_lossy().trim_end_matches(".rs").to_string());
+        let library = has_library && !matches!(module.as_deref(), Some("main" | "bin")) && !module.is_some_and(|module| binary.contains(&module));
+
+        let summary = source::analyze(&tokenizer::tokenize(&fs::read_to_string(&path)?), library);
+        unused.extend(summary.unused.iter().map(|binding| (relative.clone(), Binding { name: binding.name.clone(), line: binding.line })));
+        summaries.push((relative, summary));
+    }
+    Ok(Report { graph: Graph::new(&name, summaries), unused })
+}
+
+// A directory with a Cargo.toml is one crate, otherwise every subdirectory with one is.
+fn crates(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if dir.join("Cargo.toml").is_file() {
+        return Ok(vec![dir.to_path_buf()]);
+    }
+    let mut crates: Vec<PathBuf> = fs::read_dir(dir)?.filter_map(|entry| entry.ok().map(|entry| entry.path())).filter(|path| path.join("Cargo.toml").is_file()).collect();
+    crates.sort();
+    Ok(crates)
+}
+
+fn print_report(report: &Report) {
+    let graph = &report.graph;
+    let dead: Vec<_> = graph.unreachable().collect();
+    println!("{} ({} items, {} unreachable, {} unused bindings)", graph.name, graph.nodes.len(), dead.len(), report.unused.len());
+    if !dead.is_empty() {
+        println!("  unreachable from main:");
+        for node in dead {
+            println!("    {}:{}  {}", node.file, node.item.line, node.describe());
+        }
+    }
+    if !report.unused.is_empty() {
+        println!("  never read:");
+        for (file, binding) in &report.unused {
+            println!("    {file}:{}  {}", binding.line, binding.name);
+        }
+    }
+}
+
+fn main() {
+    let mut dot = false;
+    let mut dirs = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--dot" => dot = true,
+            dir if !dir.starts_with("--") => dirs.push(PathBuf::from(dir)),
+            _ => {
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+        }
+    }
+    if dirs.is_empty() {
+        // The repository root is the parent of this crate's directory.
+        dirs.push(Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("the crate lives inside the repository").to_path_buf());
+    }
+
+    let mut reports = Vec::new();
+    for dir in &dirs {
+        let found = crates(dir).unwrap_or_else(|error| {
+            eprintln!("Could not read {}: {error}", dir.display());
+            process::exit(2);
+        });
+        for dir in found {
+            match analyze_crate(&dir) {
+                Ok(report) => reports.push(report),
+                Err(error) => eprintln!("Skipping {}: {error}", dir.display()),
+            }
+        }
+    }
+
+    if dot {
+        let mut out = String::from("digraph calls {\n    rankdir = LR;\n    node [shape=box, fontname=\"monospace\"];\n");
+        for report in &reports {
+            report.graph.dot(&mut out);
+        }
+        out += "}";
+        println!("{out}");
+    } else {
+        for report in &reports {
+            print_report(report);
+            println!();
+        }
+    }
+}
@@ -0,0 +1,366 @@
+// Finds what the report is about in the tokens of one file: the items (functions, consts and statics), the names each of them mentions, and the variables that are bound but never read.
+//
+// It only looks at the shape of the code, without resolving names the way the compiler does. A name that is mentioned counts as a use of every item with that name, whether it is a call, a method call or a function passed as a value. That can make dead code look alive, but never the other way round.
+
+use crate::tokenizer::{Kind, Token, format_names};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    Fn,
+    Const,
+    Static,
+}
+
+#[derive(Debug)]
+pub struct Item {
+    pub kind: ItemKind,
+    pub name: String,
+    pub line: usize,
+    // Reached from outside the code being analysed: main, tests, trait methods and the public items of a library.
+    pub root: bool,
+    // Every name mentioned in the item's body.
+    pub uses: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub items: Vec<Item>,
+    // Names mentioned outside any item, e.g. in macro_rules bodies. Whatever they name is treated as reachable.
+    pub loose_uses: Vec<String>,
+    pub unused: Vec<Binding>,
+}
+
+// library: the file belongs to a library crate, so its pub items can be used by other crates and count as roots.
+pub fn analyze(tokens: &[Token], library: bool) -> Summary {
+    let mut summary = items(tokens, library);
+    summary.unused = unused_bindings(tokens);
+    summary
+}
+
+// When the body of an item ends.
+enum End {
+    // At the } that brings the brace depth back to this value.
+    Brace(usize),
+    // At the next ; at this depth, for const and static.
+    Semicolon(usize),
+}
+
+fn ident(tokens: &[Token], i: usize) -> Option<&str> {
+    tokens.get(i).filter(|token| token.kind == Kind::Ident).map(|token| token.text.as_str())
+}
+
+fn is(tokens: &[Token], i: usize, text: &str) -> bool {
+    tokens.get(i).is_some_and(|token| token.is(text))
+}
+
+fn items(tokens: &[Token], library: bool) -> Summary {
+    let mut summary = Summary::default();
+    let mut depth = 0;
+    // The items whose body is being read, innermost last. Uses go to the innermost one.
+    let mut open: Vec<(usize, End)> = Vec::new();
+    // A function whose signature has been read but whose body has not started yet.
+    let mut pending: Option<usize> = None;
+    // The depths at which the body of an impl or trait block starts, and whether its functions are trait methods.
+    let mut blocks: Vec<(usize, bool)> = Vec::new();
+    let mut block_start: Option<bool> = None;
+    let mut test_attribute = false;
+    // The name right after fn, const or static is a definition, not a use.
+    let mut definition = usize::MAX;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if i == definition {
+            continue;
+        }
+        // Keywords and definitions first, they are not uses.
+        if token.is("fn") && let Some(name) = ident(tokens, i + 1) {
+            // A function directly inside a trait, or an impl of a trait, is called through the trait, not by its name alone.
+            let trait_method = blocks.last().is_some_and(|&(block_depth, is_trait)| block_depth == depth && is_trait);
+            let public = library && is(tokens, i.wrapping_sub(1), "pub");
+            let root = name == "main" || test_attribute || trait_method || public;
+            summary.items.push(Item { kind: ItemKind::Fn, name: name.to_string(), line: token.line, root, uses: Vec::new() });
+            pending = Some(summary.items.len() - 1);
+            test_attribute = false;
+            definition = i + 1;
+            continue;
+        }
+        if (token.is("const") || token.is("static")) && let Some(name) = ident(tokens, i + 1) {
+            // const fn is a function, and mut belongs to static mut.
+            if name != "fn" && name != "mut" {
+                let kind = if token.is("const") { ItemKind::Const } else { ItemKind::Static };
+                let public = library && is(tokens, i.wrapping_sub(1), "pub");
+                summary.items.push(Item { kind, name: name.to_string(), line: token.line, root: public, uses: Vec::new() });
+                open.push((summary.items.len() - 1, End::Semicolon(depth)));
+                definition = i + 1;
+                continue;
+            }
+        }
+        if token.is("impl") || token.is("trait") {
+            // impl Display for Measurement: the for makes it a trait impl. Traits themselves only hold trait methods.
+            let header = tokens[i..].iter().take_while(|token| !token.is("{") && !token.is(";"));
+            block_start = Some(token.is("trait") || header.clone().any(|token| token.is("for")));
+        }
+        if token.is("#") && is(tokens, i + 1, "[") && is(tokens, i + 2, "test") {
+            test_attribute = true;
+        }
+
+        match token.text.as_str() {
+            "{" if token.kind == Kind::Punct => {
+                depth += 1;
+                if let Some(item) = pending.take() {
+                    open.push((item, End::Brace(depth - 1)));
+                } else if let Some(is_trait) = block_start.take() {
+                    blocks.push((depth, is_trait));
+                }
+            }
+            "}" if token.kind == Kind::Punct => {
+                depth = depth.saturating_sub(1);
+                if blocks.last().is_some_and(|&(block_depth, _)| block_depth > depth) {
+                    blocks.pop();
+                }
+                if let Some((_, End::Brace(end))) = open.last()
+                    && *end == depth
+                {
+                    open.pop();
+                }
+            }
+            ";" if token.kind == Kind::Punct => {
+                if let Some((_, End::Semicolon(end))) = open.last()
+                    && *end == depth
+                {
+                    open.pop();
+                }
+                // A function without a body, as declared in a trait. There is nothing in it to reach.
+                if let Some(item) = pending.take() {
+                    summary.items.remove(item);
+                }
+            }
+            _ => {}
+        }
+
+        let names: Vec<&str> = match token.kind {
+            Kind::Ident if !is(tokens, i + 1, "!") => vec![token.text.as_str()],
+            Kind::Str => format_names(&token.text),
+            _ => continue,
+        };
+        let uses = match open.last() {
+            Some(&(item, _)) => &mut summary.items[item].uses,
+            None => &mut summary.loose_uses,
+        };
+        uses.extend(names.into_iter().map(String::from));
+    }
+
+    summary
+}
+
+// A variable binding and the part of the file it is visible in.
+struct Site {
+    name: String,
+    line: usize,
+    // The token index of the name, and where the scope starts: after the let statement, or just inside the { of a function body.
+    index: usize,
+    start: usize,
+    depth: usize,
+    // if let, while let and for only bind for the block that follows, so they do not shadow a name for the rest of the enclosing block.
+    conditional: bool,
+}
+
+// Collects the names a pattern binds, like x and y in (x, mut y) or v in Some(v). Capitalised names are types and enum variants, a name followed by ( or { or :: is a path, and one followed by a single : is a field, as in Point { x: 0, .. }.
+fn pattern_names(tokens: &[Token], from: usize, to: usize, sites: &mut Vec<(usize, String)>) {
+    for i in from..to {
+        let Some(name) = ident(tokens, i) else { continue };
+        let is_path = is(tokens, i + 1, "(") || is(tokens, i + 1, "{") || (i + 1 < to && is(tokens, i + 1, ":")) || (i > 0 && is(tokens, i - 1, ":"));
+        if !is_path && !["mut", "ref", "_", "self"].contains(&name) && name.starts_with(|c: char| c.is_lowercase() || c == '_') && name != "_" {
+            sites.push((i, name.to_string()));
+        }
+    }
+}
+
+// The first index at or after from where one of the stop tokens appears outside brackets, or where the enclosing bracket closes.
+fn find_at_level(tokens: &[Token], from: usize, stop: &[&str]) -> usize {
+    let mut level = 0i32;
+    for (i, token) in tokens.iter().enumerate().skip(from) {
+        if token.kind != Kind::Punct && !(token.kind == Kind::Ident && stop.contains(&token.text.as_str())) {
+            continue;
+        }
+        // The colons of a path like Some::Thing are not the : of a type annotation.
+        let path = token.text == ":" && (is(tokens, i + 1, ":") || is(tokens, i.wrapping_sub(1), ":"));
+        if level == 0 && stop.contains(&token.text.as_str()) && !path {
+            return i;
+        }
+        match token.text.as_str() {
+            "(" | "[" | "{" => level += 1,
+            ")" | "]" | "}" => {
+                level -= 1;
+                if level < 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+fn binding_sites(tokens: &[Token]) -> Vec<Site> {
+    let mut sites = Vec::new();
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "{" if token.kind == Kind::Punct => depth += 1,
+            "}" if token.kind == Kind::Punct => depth -= 1,
+            _ => {}
+        }
+        if token.kind != Kind::Ident {
+            continue;
+        }
+
+        let mut names = Vec::new();
+        let (start, scope_depth, conditional) = match token.text.as_str() {
+            "let" => {
+                let pattern_end = find_at_level(tokens, i + 1, &["=", ":", ";"]);
+                pattern_names(tokens, i + 1, pattern_end, &mut names);
+                // if let and while let bind for the block that follows, a let statement for the rest of its block. Its own initializer cannot see it yet.
+                let conditional = i > 0 && ["if", "while", "&", "|"].iter().any(|keyword| is(tokens, i - 1, keyword));
+                let start = if conditional { pattern_end } else { find_at_level(tokens, i + 1, &[";"]) };
+                (start, depth, conditional)
+            }
+            "for" => {
+                // for x in ..., but not impl Trait for Type.
+                let pattern_end = find_at_level(tokens, i + 1, &["in", "{", ";"]);
+                if !is(tokens, pattern_end, "in") {
+                    continue;
+                }
+                pattern_names(tokens, i + 1, pattern_end, &mut names);
+                (pattern_end, depth, true)
+            }
+            "fn" if ident(tokens, i + 1).is_some() && is(tokens, i + 2, "(") => {
+                let close = find_at_level(tokens, i + 3, &[]);
+                let body = find_at_level(tokens, close + 1, &["{", ";"]);
+                if !is(tokens, body, "{") {
+                    continue;
+                }
+                // A parameter is the name right before the : at the top level of the list.
+                let mut level = 0;
+                for j in i + 3..close {
+                    match tokens[j].text.as_str() {
+                        "(" | "[" | "<" => level += 1,
+                        // The > of -> closes nothing.
+                        ">" if !is(tokens, j - 1, "-") => level -= 1,
+                        ")" | "]" => level -= 1,
+                        ":" if level == 0 && !is(tokens, j + 1, ":") && !is(tokens, j - 1, ":") => pattern_names(tokens, j - 1, j, &mut names),
+                        _ => {}
+                    }
+                }
+                (body + 1, depth + 1, false)
+            }
+            _ => continue,
+        };
+
+        for (index, name) in names {
+            if !name.starts_with('_') {
+                sites.push(Site { name, line: tokens[index].line, index, start, depth: scope_depth, conditional });
+            }
+        }
+    }
+    sites
+}
+
+fn unused_bindings(tokens: &[Token]) -> Vec<Binding> {
+    let sites = binding_sites(tokens);
+    let mut unused = Vec::new();
+
+    for site in &sites {
+        // Reading stops where the binding goes out of scope, or where a new binding with the same name in the same block shadows it. The new binding's initializer still sees the old one.
+        let shadowed = sites.iter().filter(|other| other.name == site.name && other.depth == site.depth && other.index > site.index && !other.conditional).map(|other| other.start).min();
+        let mut depth = site.depth;
+        let mut used = false;
+        for (i, token) in tokens.iter().enumerate().skip(site.start) {
+            if shadowed.is_some_and(|end| i > end) {
+                break;
+            }
+            match token.kind {
+                Kind::Punct if token.text == "{" => depth += 1,
+                Kind::Punct if token.text == "}" => {
+                    if depth == site.depth {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                Kind::Str => used |= format_names(&token.text).contains(&site.name.as_str()),
+                Kind::Ident if token.text == site.name => {
+                    // x.name is a field, name: a field in a struct literal, name! a macro and name:: a path. None of them read the variable.
+                    let field = i > 0 && is(tokens, i - 1, ".") && !is(tokens, i.wrapping_sub(2), ".");
+                    let label = is(tokens, i + 1, ":") && !is(tokens, i + 2, ":");
+                    let other = is(tokens, i + 1, "!") || (is(tokens, i + 1, ":") && is(tokens, i + 2, ":"));
+                    let rebinding = sites.iter().any(|other| other.index == i);
+                    used |= !(field || label || other || rebinding);
+                }
+                _ => {}
+            }
+            if used {
+                break;
+            }
+        }
+        if !used {
+            unused.push(Binding { name: site.name.clone(), line: site.line });
+        }
+    }
+    unused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::tokenize;
+
+    fn unused(source: &str) -> Vec<String> {
+        unused_bindings(&tokenize(source)).into_iter().map(|binding| binding.name).collect()
+    }
+
+    #[test]
+    fn finds_items_and_what_they_use() {
+        let source = "
+            fn main() { call_five(); }
+            fn call_five() { let x = five(); println!(\"{x}\"); }
+            fn five() -> i32 { 5 }
+            const TABLE: &[fn() -> i32] = &[five];
+            impl Display for Thing { fn fmt(&self) {} }
+            impl Thing { fn helper() {} }
+            trait Shape { fn area(&self) -> f64; }
+            #[test]
+            fn it_works() {}";
+        let summary = analyze(&tokenize(source), false);
+        let items: Vec<(&str, bool)> = summary.items.iter().map(|item| (item.name.as_str(), item.root)).collect();
+        assert_eq!(items, [("main", true), ("call_five", false), ("five", false), ("TABLE", false), ("fmt", true), ("helper", false), ("it_works", true)]);
+        assert!(summary.items[1].uses.contains(&"five".to_string()));
+        assert!(summary.items[1].uses.contains(&"x".to_string()));
+        assert!(!summary.items[1].uses.contains(&"println".to_string()));
+        assert!(summary.items[3].uses.contains(&"five".to_string()));
+    }
+
+    #[test]
+    fn public_items_of_a_library_are_roots() {
+        let summary = analyze(&tokenize("pub fn api() {} fn private() {}"), true);
+        assert_eq!(summary.items.iter().map(|item| item.root).collect::<Vec<_>>(), [true, false]);
+    }
+
+    #[test]
+    fn finds_bindings_that_are_never_read() {
+        assert_eq!(unused("fn main() { let (x, y, z) = tup; println!(\"{y}\"); }"), ["x", "z"]);
+        assert_eq!(unused("fn main() { let x = 5; let x = x + 1; }"), ["x"]);
+        assert_eq!(unused("fn f(a: i32, _b: i32, c: (i32, i32)) -> i32 { a }"), ["c"]);
+        assert_eq!(unused("fn main() { { let inner = 1; } let outer = inner; outer; }"), ["inner"]);
+        assert_eq!(unused("fn main() { if let Some(v) = o { v } for i in 0..3 {} }"), ["i"]);
+        // An if let with the same name only shadows inside its own block.
+        assert_eq!(unused("fn main() { let x = f(); if let Some(x) = x { g(x); } h(x); }"), Vec::<String>::new());
+        assert_eq!(unused("fn main() { let name = 1; S { name: 2 }.name; }"), ["name"]);
+        assert_eq!(unused("fn f(from: usize, to: usize) { for i in from..to { i; } if let S { flag: true, rest } = s {} }"), ["rest"]);
+        assert!(unused("fn main() { let name = 1; S { name }; let mut n = 0; n += 1; }").is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+// Cuts Rust source into the few kinds of tokens the analysis needs. It is not a full Rust lexer: numbers, operators and lifetimes only have to be skipped correctly, so a brace inside a string or a comment is never mistaken for the end of a block.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Ident,
+    // The contents of a string literal, without quotes. Format strings name variables inside them, as in "{x}".
+    Str,
+    // Numbers and char literals. Nothing in them matters, they only have to be skipped.
+    Literal,
+    // A single punctuation character. :: is two ':' tokens.
+    Punct,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: Kind,
+    pub text: String,
+    pub line: usize,
+}
+
+impl Token {
+    pub fn is(&self, text: &str) -> bool {
+        self.kind != Kind::Str && self.text == text
+    }
+}
+
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    // Advances past chars[i] and keeps the line number right.
+    let step = |i: &mut usize, line: &mut usize| {
+        if chars[*i] == '\n' {
+            *line += 1;
+        }
+        *i += 1;
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start_line = line;
+        let next = chars.get(i + 1).copied();
+
+        if c.is_whitespace() {
+            step(&mut i, &mut line);
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            // Block comments can be nested in Rust.
+            let mut depth = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                    depth += 1;
+                    i += 2;
+                } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    step(&mut i, &mut line);
+                }
+            }
+        } else if let Some(hashes) = raw_string_start(&chars[i..]) {
+            // r"...", r#"..."# and br"...": no escapes, the string ends at a quote followed by as many # as it started with.
+            i += chars[i..].iter().position(|&c| c == '"').expect("raw_string_start saw the quote") + 1;
+            let start = i;
+            while i < chars.len() && !(chars[i] == '"' && chars[i + 1..].iter().take(hashes).filter(|&&c| c == '#').count() == hashes) {
+                step(&mut i, &mut line);
+            }
+            tokens.push(Token { kind: Kind::Str, text: chars[start..i.min(chars.len())].iter().collect(), line: start_line });
+            i += 1 + hashes;
+        } else if c == '"' || (c == 'b' && next == Some('"')) {
+            i += if c == 'b' { 2 } else { 1 };
+            let start = i;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                if i < chars.len() {
+                    step(&mut i, &mut line);
+                }
+            }
+            tokens.push(Token { kind: Kind::Str, text: chars[start..i.min(chars.len())].iter().collect(), line: start_line });
+            i += 1;
+        } else if c == '\'' || (c == 'b' && next == Some('\'')) {
+            let quote = if c == 'b' { i + 1 } else { i };
+            // 'a' and '\n' are chars, 'a without a closing quote is a lifetime.
+            let end = if chars.get(quote + 1) == Some(&'\\') {
+                chars.get(quote + 3..).and_then(|rest| rest.iter().position(|&c| c == '\'')).map(|p| quote + 3 + p)
+            } else {
+                (chars.get(quote + 2) == Some(&'\'')).then_some(quote + 2)
+            };
+            match end {
+                Some(end) => {
+                    tokens.push(Token { kind: Kind::Literal, text: chars[i..=end].iter().collect(), line });
+                    i = end + 1;
+                }
+                None => {
+                    i = quote + 1;
+                    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                        i += 1;
+                    }
+                }
+            }
+        } else if c.is_ascii_digit() {
+            let start = i;
+            // A dot only belongs to the number if a digit follows, so the range 1..5 is not read as 1. followed by .5.
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || (chars[i] == '.' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()) && !chars[start..i].contains(&'.')))
+            {
+                i += 1;
+            }
+            tokens.push(Token { kind: Kind::Literal, text: chars[start..i].iter().collect(), line });
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let mut text: String = chars[start..i].iter().collect();
+            // r#fn is the identifier fn, written so it does not collide with the keyword.
+            if text == "r" && chars.get(i) == Some(&'#') && chars.get(i + 1).is_some_and(|c| c.is_alphabetic()) {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                text = chars[start..i].iter().collect();
+            }
+            tokens.push(Token { kind: Kind::Ident, text, line });
+        } else {
+            tokens.push(Token { kind: Kind::Punct, text: c.to_string(), line });
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+// The number of # of a raw string starting here, e.g. 1 for r#"...", or None if there is no raw string.
+fn raw_string_start(chars: &[char]) -> Option<usize> {
+    let rest = match chars {
+        ['b', 'r', rest @ ..] | ['r', rest @ ..] => rest,
+        _ => return None,
+    };
+    let hashes = rest.iter().take_while(|&&c| c == '#').count();
+    (rest.get(hashes) == Some(&'"')).then_some(hashes)
+}
+
+// The variables a format string uses directly, like x in "{x}" or "{x:>8}". {{ is an escaped brace and {0} a position, neither names anything.
+pub fn format_names(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let name = &rest[..end];
+        if name.starts_with(|c: char| c.is_alphabetic() || c == '_') && rest[end..].starts_with(['}', ':']) {
+            names.push(name);
+        }
+        rest = &rest[end..];
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(source: &str) -> Vec<String> {
+        tokenize(source).into_iter().map(|token| token.text).collect()
+    }
+
+    #[test]
+    fn skips_comments_strings_and_lifetimes() {
+        assert_eq!(texts("fn f<'a>(x: &'a str) { /* } */ g(\"}\") } // }"), [
+            "fn", "f", "<", ">", "(", "x", ":", "&", "str", ")", "{", "g", "(", "}", ")", "}"
+        ]);
+        assert_eq!(texts("let c = '}'; let d = '\\'';"), ["let", "c", "=", "'}'", ";", "let", "d", "=", "'\\''", ";"]);
+        assert_eq!(texts("r#\"say \"hi\"\"# r#match"), ["say \"hi\"", "match"]);
+        assert_eq!(texts("x.0 + 1..5 + 2.5"), ["x", ".", "0", "+", "1", ".", ".", "5", "+", "2.5"]);
+    }
+
+    #[test]
+    fn counts_lines() {
+        let tokens = tokenize("a\n\"two\nlines\"\nb");
+        assert_eq!(tokens.iter().map(|token| token.line).collect::<Vec<_>>(), [1, 2, 4]);
+        assert_eq!(tokens[1].kind, Kind::Str);
+    }
+
+    #[test]
+    fn finds_names_in_format_strings() {
+        assert_eq!(format_names("{x} and {y:>8} but not {{z}} or {0} or {}"), ["x", "y"]);
+    }
+}
@@ -0,0 +1,60 @@
+// Converts a measurement into another unit, exactly where the factors allow it.
+//
+//   cargo run --bin convert -- 5h min
+//   cargo run --bin convert -- 12 km mi
+//   cargo run --bin convert -- 100F C
+//
+// Every binary in src/bin is its own crate. It reaches the library of the package, src/lib.rs, by the package name, just like src/main.rs does.
+
+use std::env;
+use std::process;
+
+use functions::measurement::{self, Measurement, MeasurementError, Unit};
+use functions::ratio::Ratio;
+
+const USAGE: &str = "usage: convert QUANTITY UNIT, e.g. convert 5h min, convert 12 km mi or convert 100F to C";
+
+// The exact digits if there are finitely many. Otherwise six decimals, followed by the exact fraction.
+fn describe(value: Ratio, unit: Unit) -> String {
+    let symbol = unit.symbol();
+    match value.to_decimal() {
+        Some(decimal) => format!("{decimal} {symbol}"),
+        None => {
+            let rounded = format!("{:.6}", value.to_f64());
+            let rounded = rounded.trim_end_matches('0').trim_end_matches('.');
+            format!("{rounded} {symbol} (exactly {value} {symbol})")
+        }
+    }
+}
+
+fn convert(quantity: &str, target: &str) -> Result<String, MeasurementError> {
+    let (number, from) = measurement::split(quantity)?;
+    let to = Unit::parse(target).ok_or_else(|| MeasurementError::UnknownUnit(target.to_string()))?;
+    let value = Ratio::parse(number).ok_or_else(|| MeasurementError::MissingNumber(quantity.to_string()))?;
+
+    // The input is printed through Measurement's Display, the same way the lesson prints its measurements.
+    let input = Measurement::new(value.to_f64(), from);
+    let output = measurement::convert_exact(value, from, to)?;
+    Ok(format!("{input} = {}", describe(output, to)))
+}
+
+fn main() {
+    // "to" between the quantity and the unit is optional, and the number and its unit may be separate arguments.
+    let args: Vec<String> = env::args().skip(1).filter(|arg| arg != "to").collect();
+    let (quantity, target) = match args.as_slice() {
+        [quantity, target] => (quantity.clone(), target),
+        [number, unit, target] => (format!("{number}{unit}"), target),
+        _ => {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    match convert(&quantity, target) {
+        Ok(line) => println!("{line}"),
+        Err(error) => {
+            eprintln!("error: {error}");
+            process::exit(1);
+        }
+    }
+}
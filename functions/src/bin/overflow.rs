@@ -0,0 +1,75 @@
+// Runs plus_one, minus_one and double right at the limits of the integer types, once with every overflow policy, and prints the results next to each other.
+//
+//   cargo run --bin overflow            every integer type
+//   cargo run --bin overflow -- i8 u64  only these
+//   cargo run --release --bin overflow  the plain operators then wrap instead of panicking
+
+use std::env;
+use std::process;
+
+use functions::overflow::{self, Integer, Op, Policy};
+
+const TYPES: [&str; 12] = ["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+
+// One row: the call, what the plain operator does in this build, and the result of every policy.
+fn row<T: Integer>(name: &str, op: Op, x: T, other: T) -> Vec<String> {
+    let mut cells = vec![format!("{name}({x})"), overflow::unchecked_behaviour(op, x, other)];
+    cells.extend(Policy::ALL.iter().map(|&policy| overflow::apply(policy, op, x, other).to_string()));
+    cells
+}
+
+fn table<T: Integer>() {
+    let below_max = T::MAX.wrapping(Op::Sub, T::ONE);
+    let above_min = T::MIN.wrapping(Op::Add, T::ONE);
+    // MAX / 2 + 1 is the smallest value whose double does not fit.
+    let half = T::MAX.wrapping(Op::Div, T::TWO).wrapping(Op::Add, T::ONE);
+    let mut rows = vec![
+        row("plus_one", Op::Add, below_max, T::ONE),
+        row("plus_one", Op::Add, T::MAX, T::ONE),
+        row("minus_one", Op::Sub, above_min, T::ONE),
+        row("minus_one", Op::Sub, T::MIN, T::ONE),
+    ];
+    rows.push(row("double", Op::Mul, half, T::TWO));
+
+    let mut header = vec!["call".to_string(), "plain operator".to_string()];
+    header.extend(Policy::ALL.iter().map(Policy::to_string));
+    rows.insert(0, header);
+
+    // Every column is as wide as its widest cell, so the columns line up for i8 as well as for u128.
+    let widths: Vec<usize> = (0..rows[0].len()).map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0)).collect();
+    println!("{}", T::NAME);
+    for row in rows {
+        let cells: Vec<String> = row.iter().zip(&widths).map(|(cell, &width)| format!("{cell:<width$}")).collect();
+        println!("  {}", cells.join("  ").trim_end());
+    }
+    println!();
+}
+
+fn main() {
+    let mut chosen: Vec<String> = env::args().skip(1).collect();
+    if let Some(unknown) = chosen.iter().find(|name| !TYPES.contains(&name.as_str())) {
+        eprintln!("`{unknown}` is not an integer type, choose from {}", TYPES.join(" "));
+        process::exit(2);
+    }
+    if chosen.is_empty() {
+        chosen = TYPES.iter().map(|name| name.to_string()).collect();
+    }
+
+    // A generic function needs its type at compile time, so the name typed on the command line is matched to a type here.
+    for name in chosen {
+        match name.as_str() {
+            "i8" => table::<i8>(),
+            "i16" => table::<i16>(),
+            "i32" => table::<i32>(),
+            "i64" => table::<i64>(),
+            "i128" => table::<i128>(),
+            "isize" => table::<isize>(),
+            "u8" => table::<u8>(),
+            "u16" => table::<u16>(),
+            "u32" => table::<u32>(),
+            "u64" => table::<u64>(),
+            "u128" => table::<u128>(),
+            _ => table::<usize>(),
+        }
+    }
+}
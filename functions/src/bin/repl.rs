@@ -0,0 +1,142 @@
+// An interactive prompt for the interpreter in src/interpreter.rs. Every input is run and its value is printed with its type, so it is easy to see which lines are expressions and which are statements:
+//
+//   >> { let x = 3; x + 1 }
+//   => 4 : i32
+//   >> { let x = 3; x + 1; }
+//   => () : unit
+//
+//   cargo run --bin repl
+//
+// Definitions stay around, a function or variable from one line can be used on the next. An input with unclosed braces continues on the next line.
+
+use std::io::{self, BufRead, Write};
+
+use functions::interpreter::{Interpreter, Type, Value};
+
+const HELP: &str = "\
+Type statements and expressions, e.g. let y = 6; or fn five() -> i32 { 5 } or five() + 1.
+  :env     lists the variables and functions defined so far
+  :reset   forgets all of them
+  :help    shows this text
+  :quit    ends the session, so does Ctrl-D";
+
+// How many braces are still open. Braces in // comments do not count.
+fn open_braces(source: &str) -> i32 {
+    source
+        .lines()
+        .flat_map(|line| line.split("//").next().unwrap_or("").chars())
+        .map(|c| match c {
+            '{' => 1,
+            '}' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+// The unit type is written (), just like its only value. unit reads better next to it than => () : (), but only in the => line, which is not Rust.
+fn type_name(value: Value) -> String {
+    match value.type_of() {
+        Type::Unit => "unit".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// A variable as the let statement that would define it. This is Rust syntax, so the unit type is spelled ().
+fn declaration(name: &str, value: Value) -> String {
+    format!("let {name}: {} = {value}", value.type_of())
+}
+
+fn show_env(interpreter: &Interpreter) {
+    let (variables, functions) = (interpreter.variables(), interpreter.functions());
+    if variables.is_empty() && functions.is_empty() {
+        println!("Nothing is defined yet.");
+    }
+    for (name, value) in variables {
+        println!("  {}", declaration(name, value));
+    }
+    for signature in functions {
+        println!("  {signature}");
+    }
+}
+
+fn prompt(text: &str) {
+    print!("{text}");
+    let _ = io::stdout().flush();
+}
+
+fn main() {
+    println!("Rust-like expressions, :help for help.");
+    let mut interpreter = Interpreter::new();
+    let mut input = String::new();
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        prompt(if input.is_empty() { ">> " } else { ".. " });
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            break;
+        };
+
+        if input.is_empty() {
+            match line.trim() {
+                "" => continue,
+                ":quit" | ":q" => break,
+                ":help" => {
+                    println!("{HELP}");
+                    continue;
+                }
+                ":env" => {
+                    show_env(&interpreter);
+                    continue;
+                }
+                ":reset" => {
+                    interpreter = Interpreter::new();
+                    println!("Forgot all definitions.");
+                    continue;
+                }
+                command if command.starts_with(':') => {
+                    println!("Unknown command {command}, :help lists the commands.");
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        input += &line;
+        input += "\n";
+        if open_braces(&input) > 0 {
+            continue;
+        }
+
+        match interpreter.run(&input) {
+            Ok(value) => println!("=> {value} : {}", type_name(value)),
+            Err(error) => println!("error: {}", error.render(input.trim_end())),
+        }
+        input.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_open_braces() {
+        assert_eq!(open_braces("fn five() -> i32 {"), 1);
+        assert_eq!(open_braces("fn five() -> i32 {\n    5\n}"), 0);
+        assert_eq!(open_braces("{ { // }"), 2);
+        assert_eq!(open_braces("}"), -1);
+    }
+
+    #[test]
+    fn calls_the_unit_type_unit() {
+        assert_eq!(type_name(Value::I32(4)), "i32");
+        assert_eq!(type_name(Value::Unit), "unit");
+    }
+
+    #[test]
+    fn lists_variables_as_rust() {
+        assert_eq!(declaration("x", Value::I32(4)), "let x: i32 = 4");
+        assert_eq!(declaration("u", Value::Unit), "let u: () = ()");
+    }
+}
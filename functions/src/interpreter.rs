@@ -0,0 +1,186 @@
+// A small interpreter for the part of Rust this lesson is about: let statements, blocks, arithmetic on i32, function definitions and calls, and the rule that a block's value is its last expression unless that ends with a semicolon.
+//
+//   let y = { let x = 3; x + 1 };   y is 4
+//   { let x = 3; x + 1; }           the value is (), the semicolon made x + 1 a statement
+//   let x = (let y = 6);            an error, let is a statement and has no value
+//
+// It works in three steps, one module each. The lexer cuts the source into tokens, the parser builds a tree of statements and expressions from them, and the evaluator walks that tree.
+
+mod eval;
+mod lexer;
+mod parser;
+
+use std::fmt;
+
+pub use eval::Interpreter;
+
+// Positions count characters from the start of the source, so the caret in an error message lines up under the right character.
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub position: usize,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(position: usize, message: impl Into<String>) -> Error {
+        Error { position, message: message.into() }
+    }
+
+    // Shows the line of the source the error is on with a caret under the offending character.
+    pub fn render(&self, source: &str) -> String {
+        let mut start = 0;
+        for line in source.split('\n') {
+            let length = line.chars().count();
+            if self.position <= start + length {
+                return format!("{}\n  {line}\n  {}^", self.message, " ".repeat(self.position - start));
+            }
+            start += length + 1;
+        }
+        self.message.clone()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position + 1)
+    }
+}
+
+// The types a value can have. Functions are not values here, they can only be called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    I32,
+    // The unit type, written (). It has exactly one value, also written (), and is what statements and empty blocks produce.
+    Unit,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::I32 => write!(f, "i32"),
+            Type::Unit => write!(f, "()"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    I32(i32),
+    Unit,
+}
+
+impl Value {
+    pub fn type_of(self) -> Type {
+        match self {
+            Value::I32(_) => Type::I32,
+            Value::Unit => Type::Unit,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::I32(n) => write!(f, "{n}"),
+            Value::Unit => write!(f, "()"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(source: &str) -> Result<Value, String> {
+        Interpreter::new().run(source).map_err(|error| error.message)
+    }
+
+    #[test]
+    fn a_block_is_worth_its_last_expression() {
+        assert_eq!(run("let y = { let x = 3; x + 1 }; y"), Ok(Value::I32(4)));
+        assert_eq!(run("{ let x = 3; x + 1 }"), Ok(Value::I32(4)));
+        assert_eq!(run("2 + 3 * (4 - 1) % 5 - -1"), Ok(Value::I32(7)));
+    }
+
+    #[test]
+    fn a_semicolon_turns_an_expression_into_a_statement() {
+        assert_eq!(run("let x = 1; { x + 1; }"), Ok(Value::Unit));
+        assert_eq!(run("let y = 6;"), Ok(Value::Unit));
+        assert_eq!(run("{}"), Ok(Value::Unit));
+        assert_eq!(run("let u: () = { 5; }; u"), Ok(Value::Unit));
+    }
+
+    #[test]
+    fn let_is_not_an_expression() {
+        assert_eq!(run("let x = (let y = 6);"), Err("expected expression, found `let` statement".to_string()));
+        assert!(run("let x = let y = 6;").is_err());
+    }
+
+    #[test]
+    fn calls_functions_defined_anywhere_in_the_block() {
+        let source = "
+            fn main() -> i32 { plus_one(five()) }
+            fn five() -> i32 { 5 }
+            fn plus_one(x: i32) -> i32 { x + 1 }
+            main()";
+        assert_eq!(run(source), Ok(Value::I32(6)));
+        assert_eq!(run("fn nothing() {} nothing()"), Ok(Value::Unit));
+    }
+
+    #[test]
+    fn checks_return_and_argument_types() {
+        assert_eq!(
+            run("fn plus_one(x: i32) -> i32 { x + 1; }"),
+            Err("mismatched types: expected `i32`, found `()`, remove the semicolon after the last expression to return its value".to_string())
+        );
+        assert_eq!(run("fn f() -> i32 { { 1; } } f()"), Err("mismatched types: expected `i32`, found `()`".to_string()));
+        assert_eq!(run("fn f(x: i32) {} f(())"), Err("mismatched types: expected `i32`, found `()`".to_string()));
+        assert_eq!(run("fn f(x: i32) {} f(1, 2)"), Err("this function takes 1 argument but 2 arguments were supplied".to_string()));
+        assert_eq!(run("let x: i32 = {};"), Err("mismatched types: expected `i32`, found `()`".to_string()));
+        assert_eq!(run("1 + {}"), Err("cannot apply `+` to `i32` and `()`".to_string()));
+    }
+
+    #[test]
+    fn functions_cannot_see_the_callers_variables() {
+        assert_eq!(run("let x = 5; fn f() -> i32 { x } f()"), Err("cannot find value `x` in this scope".to_string()));
+        assert_eq!(run("{ let x = 5; } x"), Err("cannot find value `x` in this scope".to_string()));
+        assert_eq!(run("g()"), Err("cannot find function `g` in this scope".to_string()));
+    }
+
+    #[test]
+    fn shadowing_and_scopes() {
+        assert_eq!(run("let x = 5; let x = x + 1; { let x = x * 2; x } + x"), Ok(Value::I32(18)));
+    }
+
+    #[test]
+    fn arithmetic_is_checked() {
+        assert_eq!(run("2147483647 + 1"), Err("attempt to add with overflow".to_string()));
+        assert_eq!(run("1 / (2 - 2)"), Err("attempt to divide by zero".to_string()));
+        assert_eq!(run("2147483648"), Err("integer literal is too large for `i32`".to_string()));
+        assert_eq!(run("-2147483648"), Ok(Value::I32(i32::MIN)));
+        assert_eq!(run("-2147483648 - 1"), Err("attempt to subtract with overflow".to_string()));
+        assert_eq!(run("-(2147483648)"), Err("integer literal is too large for `i32`".to_string()));
+        assert_eq!(run("- -2147483648"), Err("attempt to negate with overflow".to_string()));
+        assert_eq!(run("4294967296"), Err("integer literal is too large for `i32`".to_string()));
+        assert_eq!(run("fn f() -> i32 { f() } f()"), Err("recursion limit reached while calling `f`".to_string()));
+    }
+
+    #[test]
+    fn points_at_the_error() {
+        let source = "let a = 1;\nlet b = a +;";
+        let error = Interpreter::new().run(source).unwrap_err();
+        assert_eq!(error.render(source), "expected expression, found `;`\n  let b = a +;\n             ^");
+    }
+
+    #[test]
+    fn remembers_definitions_between_runs() {
+        let mut interpreter = Interpreter::new();
+        assert_eq!(interpreter.run("fn five() -> i32 { 5 }"), Ok(Value::Unit));
+        assert_eq!(interpreter.run("let x = five();"), Ok(Value::Unit));
+        assert_eq!(interpreter.run("x + five()"), Ok(Value::I32(10)));
+
+        interpreter.run("let x = 1; let y = x; let x = (); fn five() -> i32 { 6 }").unwrap();
+        assert_eq!(interpreter.variables(), [("y", Value::I32(1)), ("x", Value::Unit)]);
+        assert_eq!(interpreter.functions(), ["fn five() -> i32"]);
+    }
+}
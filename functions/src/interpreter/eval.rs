@@ -0,0 +1,218 @@
+// Walks the syntax tree and computes values. Variables live in a stack of scopes: every block pushes a scope when it starts and pops it when it ends, which is why a variable from inside a block is gone after the closing brace.
+
+use std::rc::Rc;
+
+use super::parser::{Block, Expression, Function, Statement, parse};
+use super::{Error, Type, Value};
+
+// Every call nests the evaluator one level deeper on the real stack. Without if there is no way to stop a recursion, so this only turns an endless one into an error.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Default)]
+struct Scope {
+    // Later entries shadow earlier ones with the same name, so lookups search from the back.
+    variables: Vec<(String, Value)>,
+    functions: Vec<Rc<Function>>,
+}
+
+pub struct Interpreter {
+    // The first scope is the top level. It outlives a single run, so definitions made in one run can be used in the next.
+    scopes: Vec<Scope>,
+    depth: usize,
+}
+
+impl Default for Interpreter {
+    fn default() -> Interpreter {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter { scopes: vec![Scope::default()], depth: 0 }
+    }
+
+    // Runs source as the body of a block at the top level and returns its value.
+    pub fn run(&mut self, source: &str) -> Result<Value, Error> {
+        let program = parse(source)?;
+        // An error can leave scopes of unfinished blocks behind, only the top level is kept.
+        let result = self.statements(&program);
+        self.scopes.truncate(1);
+        self.depth = 0;
+        result
+    }
+
+    // The variables of the top level that can still be used, in the order they were defined. Shadowed ones are left out.
+    pub fn variables(&self) -> Vec<(&str, Value)> {
+        let variables = &self.scopes[0].variables;
+        variables
+            .iter()
+            .enumerate()
+            .filter(|(i, (name, _))| !variables[i + 1..].iter().any(|(later, _)| later == name))
+            .map(|(_, (name, value))| (name.as_str(), *value))
+            .collect()
+    }
+
+    // The signatures of the functions defined at the top level, the same way.
+    pub fn functions(&self) -> Vec<String> {
+        let functions = &self.scopes[0].functions;
+        functions
+            .iter()
+            .enumerate()
+            .filter(|(i, function)| !functions[i + 1..].iter().any(|later| later.name == function.name))
+            .map(|(_, function)| function.signature())
+            .collect()
+    }
+
+    fn statements(&mut self, block: &Block) -> Result<Value, Error> {
+        // Functions can be called before their definition, as long as it is in the same block, so all of them are defined before the first statement runs.
+        for statement in &block.statements {
+            if let Statement::Function(function) = statement {
+                check_signature(function)?;
+                self.scope().functions.push(Rc::clone(function));
+            }
+        }
+
+        for statement in &block.statements {
+            match statement {
+                Statement::Let { name, annotation, value, position } => {
+                    let value = self.evaluate(value)?;
+                    if let Some(expected) = annotation {
+                        expect(*expected, value, *position)?;
+                    }
+                    self.scope().variables.push((name.clone(), value));
+                }
+                Statement::Function(_) => {}
+                Statement::Expression { expression, semicolon } => {
+                    let value = self.evaluate(expression)?;
+                    // A block without a semicolon is still a statement, but only if it has no value to lose.
+                    if !semicolon {
+                        expect(Type::Unit, value, expression.position().unwrap_or(0))?;
+                    }
+                }
+            }
+        }
+
+        match &block.tail {
+            Some(tail) => self.evaluate(tail),
+            None => Ok(Value::Unit),
+        }
+    }
+
+    fn scope(&mut self) -> &mut Scope {
+        self.scopes.last_mut().expect("the top level scope is never removed")
+    }
+
+    fn block(&mut self, block: &Block) -> Result<Value, Error> {
+        self.scopes.push(Scope::default());
+        let value = self.statements(block);
+        self.scopes.pop();
+        value
+    }
+
+    fn evaluate(&mut self, expression: &Expression) -> Result<Value, Error> {
+        match expression {
+            Expression::Integer(n) => Ok(Value::I32(*n)),
+            Expression::Unit => Ok(Value::Unit),
+            Expression::Variable { name, position } => self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.variables.iter().rev().find(|(variable, _)| variable == name))
+                .map(|(_, value)| *value)
+                .ok_or_else(|| Error::new(*position, format!("cannot find value `{name}` in this scope"))),
+            Expression::Negate { operand, position } => match self.evaluate(operand)? {
+                Value::I32(n) => n.checked_neg().map(Value::I32).ok_or_else(|| Error::new(*position, "attempt to negate with overflow")),
+                Value::Unit => Err(Error::new(*position, "cannot apply unary `-` to `()`")),
+            },
+            Expression::Binary { operator, left, right, position } => {
+                let (left, right) = (self.evaluate(left)?, self.evaluate(right)?);
+                arithmetic(*operator, left, right).map_err(|message| Error::new(*position, message))
+            }
+            Expression::Block(block) => self.block(block),
+            Expression::Call { name, arguments, position } => self.call(name, arguments, *position),
+        }
+    }
+
+    fn call(&mut self, name: &str, arguments: &[Expression], position: usize) -> Result<Value, Error> {
+        let function = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.functions.iter().rev().find(|function| function.name == name))
+            .cloned()
+            .ok_or_else(|| Error::new(position, format!("cannot find function `{name}` in this scope")))?;
+
+        if arguments.len() != function.parameters.len() {
+            let plural = |n: usize| if n == 1 { "" } else { "s" };
+            let (wanted, given) = (function.parameters.len(), arguments.len());
+            return Err(Error::new(
+                position,
+                format!("this function takes {wanted} argument{} but {given} argument{} {} supplied", plural(wanted), plural(given), if given == 1 { "was" } else { "were" }),
+            ));
+        }
+
+        // The arguments are evaluated in the caller's scopes, left to right.
+        let mut parameters = Scope::default();
+        for ((parameter, expected), argument) in function.parameters.iter().zip(arguments) {
+            let value = self.evaluate(argument)?;
+            expect(*expected, value, argument.position().unwrap_or(position))?;
+            parameters.variables.push((parameter.clone(), value));
+        }
+
+        if self.depth == MAX_DEPTH {
+            return Err(Error::new(position, format!("recursion limit reached while calling `{name}`")));
+        }
+
+        // A function body sees its parameters and the functions around it, but not the caller's variables. Rust functions are not closures, they capture nothing.
+        let mut scopes: Vec<Scope> = self.scopes.iter().map(|scope| Scope { variables: Vec::new(), functions: scope.functions.clone() }).collect();
+        scopes.push(parameters);
+        let caller = std::mem::replace(&mut self.scopes, scopes);
+        self.depth += 1;
+        let value = self.statements(&function.body);
+        self.depth -= 1;
+        self.scopes = caller;
+
+        let value = value?;
+        expect(function.returns, value, function.position)?;
+        Ok(value)
+    }
+}
+
+// A body without a final expression is worth (). If the signature promises something else, that is wrong before the function is ever called.
+fn check_signature(function: &Function) -> Result<(), Error> {
+    if function.returns == Type::Unit || function.body.tail.is_some() {
+        return Ok(());
+    }
+    let mut message = format!("mismatched types: expected `{}`, found `()`", function.returns);
+    if let Some(Statement::Expression { semicolon: true, .. }) = function.body.statements.last() {
+        message += ", remove the semicolon after the last expression to return its value";
+    }
+    Err(Error::new(function.position, message))
+}
+
+fn expect(expected: Type, value: Value, position: usize) -> Result<(), Error> {
+    if value.type_of() == expected {
+        Ok(())
+    } else {
+        Err(Error::new(position, format!("mismatched types: expected `{expected}`, found `{}`", value.type_of())))
+    }
+}
+
+// Overflow is an error, like in a debug build of a real Rust program, which panics with the same messages.
+fn arithmetic(operator: char, left: Value, right: Value) -> Result<Value, String> {
+    let (Value::I32(a), Value::I32(b)) = (left, right) else {
+        return Err(format!("cannot apply `{operator}` to `{}` and `{}`", left.type_of(), right.type_of()));
+    };
+    let result = match operator {
+        '+' => a.checked_add(b).ok_or("attempt to add with overflow"),
+        '-' => a.checked_sub(b).ok_or("attempt to subtract with overflow"),
+        '*' => a.checked_mul(b).ok_or("attempt to multiply with overflow"),
+        '/' if b == 0 => Err("attempt to divide by zero"),
+        '/' => a.checked_div(b).ok_or("attempt to divide with overflow"),
+        '%' if b == 0 => Err("attempt to calculate the remainder with a divisor of zero"),
+        '%' => a.checked_rem(b).ok_or("attempt to calculate the remainder with overflow"),
+        _ => unreachable!("the parser only creates + - * / %"),
+    };
+    result.map(Value::I32).map_err(String::from)
+}
@@ -0,0 +1,105 @@
+// Cuts the source into tokens: numbers, names, keywords and punctuation. Whitespace and // comments only separate tokens and are dropped.
+
+use super::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    // A u32, not an i32: the lexer sees the 2147483648 of -2147483648 without its minus sign, and that literal does not fit in an i32 on its own.
+    Integer(u32),
+    Name(String),
+    Let,
+    Fn,
+    // ->, between the parameters and the return type of a function.
+    Arrow,
+    Symbol(char),
+    End,
+}
+
+impl Token {
+    // How the token is written, for error messages like expected `;`, found `}`.
+    pub fn describe(&self) -> String {
+        match self {
+            Token::Integer(n) => format!("`{n}`"),
+            Token::Name(name) => format!("`{name}`"),
+            Token::Let => "`let` statement".to_string(),
+            Token::Fn => "`fn` item".to_string(),
+            Token::Arrow => "`->`".to_string(),
+            Token::Symbol(c) => format!("`{c}`"),
+            Token::End => "end of input".to_string(),
+        }
+    }
+}
+
+// Every token comes with the position of its first character. The list always ends with Token::End, so the parser never runs past it.
+pub fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, Error> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '_') {
+                i += 1;
+            }
+            let digits: String = chars[start..i].iter().filter(|&&c| c != '_').collect();
+            // Every literal is an i32 here, the only integer type there is. The parser checks the range, once it knows whether a minus sign comes first.
+            let value = digits.parse().map_err(|_| Error::new(start, "integer literal is too large for `i32`"))?;
+            tokens.push((start, Token::Integer(value)));
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let token = match word.as_str() {
+                "let" => Token::Let,
+                "fn" => Token::Fn,
+                _ => Token::Name(word),
+            };
+            tokens.push((start, token));
+        } else if c == '-' && chars.get(i + 1) == Some(&'>') {
+            tokens.push((start, Token::Arrow));
+            i += 2;
+        } else if "+-*/%=(){};:,".contains(c) {
+            tokens.push((start, Token::Symbol(c)));
+            i += 1;
+        } else {
+            return Err(Error::new(start, format!("unknown start of token: {c}")));
+        }
+    }
+
+    tokens.push((chars.len(), Token::End));
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_a_function_into_tokens() {
+        let tokens: Vec<Token> = tokenize("fn f(x: i32) -> i32 { x + 1_000 } // done").unwrap().into_iter().map(|(_, token)| token).collect();
+        let name = |n: &str| Token::Name(n.to_string());
+        assert_eq!(
+            tokens,
+            [
+                Token::Fn, name("f"), Token::Symbol('('), name("x"), Token::Symbol(':'), name("i32"), Token::Symbol(')'),
+                Token::Arrow, name("i32"), Token::Symbol('{'), name("x"), Token::Symbol('+'), Token::Integer(1000), Token::Symbol('}'),
+                Token::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_characters() {
+        assert_eq!(tokenize("let x = 5 $ 3;"), Err(Error::new(10, "unknown start of token: $")));
+    }
+}
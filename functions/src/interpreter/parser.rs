@@ -0,0 +1,374 @@
+// Builds the syntax tree with a recursive descent parser. Each grammar rule becomes one function:
+//
+// program    := body END
+// body       := (let | function | expression ';' | block)* expression?
+// let        := 'let' name (':' type)? '=' expression ';'
+// function   := 'fn' name '(' (name ':' type (',' name ':' type)*)? ')' ('->' type)? block
+// type       := 'i32' | '(' ')'
+// expression := term (('+' | '-') term)*
+// term       := factor (('*' | '/' | '%') factor)*
+// factor     := '-' factor | primary
+// primary    := integer | name | name '(' arguments ')' | '(' ')' | '(' expression ')' | block
+// block      := '{' body '}'
+//
+// The rule that matters most for the lesson: expression never starts with let. A let can only be a statement, so let x = (let y = 6); fails right here, before anything runs.
+
+use std::rc::Rc;
+
+use super::Error;
+use super::Type;
+use super::lexer::{Token, tokenize};
+
+#[derive(Debug)]
+pub struct Block {
+    pub statements: Vec<Statement>,
+    // The expression after the last statement, if it has no semicolon. It is the value of the block, without one the block is worth ().
+    pub tail: Option<Box<Expression>>,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub parameters: Vec<(String, Type)>,
+    pub returns: Type,
+    pub body: Block,
+    pub position: usize,
+}
+
+impl Function {
+    // The first line of the definition, e.g. fn plus_one(x: i32) -> i32.
+    pub fn signature(&self) -> String {
+        let parameters: Vec<String> = self.parameters.iter().map(|(name, ty)| format!("{name}: {ty}")).collect();
+        let returns = if self.returns == Type::Unit { String::new() } else { format!(" -> {}", self.returns) };
+        format!("fn {}({}){returns}", self.name, parameters.join(", "))
+    }
+}
+
+#[derive(Debug)]
+pub enum Statement {
+    Let { name: String, annotation: Option<Type>, value: Expression, position: usize },
+    // Rc, because the scope the function is defined in keeps a reference to it as long as it can be called.
+    Function(Rc<Function>),
+    // An expression whose value is thrown away. Blocks may stand alone without a semicolon, but then they must be worth ().
+    Expression { expression: Expression, semicolon: bool },
+}
+
+#[derive(Debug)]
+pub enum Expression {
+    Integer(i32),
+    Unit,
+    Variable { name: String, position: usize },
+    Call { name: String, arguments: Vec<Expression>, position: usize },
+    Negate { operand: Box<Expression>, position: usize },
+    Binary { operator: char, left: Box<Expression>, right: Box<Expression>, position: usize },
+    Block(Block),
+}
+
+impl Expression {
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            Expression::Variable { position, .. }
+            | Expression::Call { position, .. }
+            | Expression::Negate { position, .. }
+            | Expression::Binary { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+// How deep expressions may nest: parentheses, blocks, calls, minus signs and the operands of a chain like 1 + 2 + 3 all count. The parser, the evaluator and even dropping the tree recurse once per level, so ((((...)))) from the REPL could otherwise overflow the stack.
+const MAX_NESTING: usize = 64;
+
+pub fn parse(source: &str) -> Result<Block, Error> {
+    let mut parser = Parser { tokens: tokenize(source)?, next: 0, depth: 0 };
+    let program = parser.body()?;
+    parser.expect(Token::End)?;
+    Ok(program)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    // The nesting level of the expression being parsed, see MAX_NESTING.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        // tokenize ends every list with Token::End, and the parser never moves past it.
+        &self.tokens[self.next].1
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.next].0
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if token != Token::End {
+            self.next += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        let found = *self.peek() == token;
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), Error> {
+        if self.eat(token.clone()) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&token.describe()))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> Error {
+        Error::new(self.position(), format!("expected {expected}, found {}", self.peek().describe()))
+    }
+
+    // Goes one level deeper. The callers put self.depth back when they are done with the level.
+    fn nest(&mut self, position: usize) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err(Error::new(position, "expression nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        match self.peek().clone() {
+            Token::Name(name) => {
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.unexpected("a name")),
+        }
+    }
+
+    fn body(&mut self) -> Result<Block, Error> {
+        let mut statements = Vec::new();
+        loop {
+            match self.peek() {
+                Token::End | Token::Symbol('}') => return Ok(Block { statements, tail: None }),
+                Token::Let => statements.push(self.let_statement()?),
+                Token::Fn => statements.push(Statement::Function(Rc::new(self.function()?))),
+                _ => {
+                    let expression = self.expression()?;
+                    if self.eat(Token::Symbol(';')) {
+                        statements.push(Statement::Expression { expression, semicolon: true });
+                    } else if matches!(self.peek(), Token::End | Token::Symbol('}')) {
+                        return Ok(Block { statements, tail: Some(Box::new(expression)) });
+                    } else if matches!(expression, Expression::Block(_)) {
+                        statements.push(Statement::Expression { expression, semicolon: false });
+                    } else {
+                        return Err(self.unexpected("`;`"));
+                    }
+                }
+            }
+        }
+    }
+
+    fn let_statement(&mut self) -> Result<Statement, Error> {
+        let position = self.position();
+        self.expect(Token::Let)?;
+        let name = self.name()?;
+        let annotation = if self.eat(Token::Symbol(':')) { Some(self.type_name()?) } else { None };
+        self.expect(Token::Symbol('='))?;
+        let value = self.expression()?;
+        self.expect(Token::Symbol(';'))?;
+        Ok(Statement::Let { name, annotation, value, position })
+    }
+
+    fn function(&mut self) -> Result<Function, Error> {
+        let position = self.position();
+        self.expect(Token::Fn)?;
+        let name = self.name()?;
+        self.expect(Token::Symbol('('))?;
+        let mut parameters = Vec::new();
+        while !self.eat(Token::Symbol(')')) {
+            if !parameters.is_empty() {
+                self.expect(Token::Symbol(','))?;
+            }
+            let parameter = self.name()?;
+            // Parameters always need a type, the signature is the one place Rust never infers one.
+            self.expect(Token::Symbol(':'))?;
+            parameters.push((parameter, self.type_name()?));
+        }
+        let returns = if self.eat(Token::Arrow) { self.type_name()? } else { Type::Unit };
+        // A function body is a block too, and functions can be defined inside each other.
+        self.nest(position)?;
+        let body = self.block()?;
+        self.depth -= 1;
+        Ok(Function { name, parameters, returns, body, position })
+    }
+
+    fn type_name(&mut self) -> Result<Type, Error> {
+        if self.eat(Token::Symbol('(')) {
+            self.expect(Token::Symbol(')'))?;
+            return Ok(Type::Unit);
+        }
+        let position = self.position();
+        match self.name()?.as_str() {
+            "i32" => Ok(Type::I32),
+            other => Err(Error::new(position, format!("unknown type `{other}`, only i32 and () exist here"))),
+        }
+    }
+
+    fn block(&mut self) -> Result<Block, Error> {
+        self.expect(Token::Symbol('{'))?;
+        let block = self.body()?;
+        self.expect(Token::Symbol('}'))?;
+        Ok(block)
+    }
+
+    // 1 + 2 + 3 is parsed in a loop, but becomes ((1 + 2) + 3), so every operator makes the tree one level deeper.
+    fn expression(&mut self) -> Result<Expression, Error> {
+        let depth = self.depth;
+        let mut left = self.term()?;
+        while let Token::Symbol(operator @ ('+' | '-')) = *self.peek() {
+            let position = self.position();
+            self.advance();
+            self.nest(position)?;
+            let right = self.term()?;
+            left = Expression::Binary { operator, left: Box::new(left), right: Box::new(right), position };
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expression, Error> {
+        let depth = self.depth;
+        let mut left = self.factor()?;
+        while let Token::Symbol(operator @ ('*' | '/' | '%')) = *self.peek() {
+            let position = self.position();
+            self.advance();
+            self.nest(position)?;
+            let right = self.factor()?;
+            left = Expression::Binary { operator, left: Box::new(left), right: Box::new(right), position };
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Expression, Error> {
+        let position = self.position();
+        if self.eat(Token::Symbol('-')) {
+            // -2147483648 is i32::MIN, even though 2147483648 alone does not fit. Like rustc, a minus sign right before a literal makes it one negative literal.
+            if let Token::Integer(n) = *self.peek()
+                && n == i32::MIN.unsigned_abs()
+            {
+                self.advance();
+                return Ok(Expression::Integer(i32::MIN));
+            }
+            self.nest(position)?;
+            let operand = self.factor()?;
+            self.depth -= 1;
+            return Ok(Expression::Negate { operand: Box::new(operand), position });
+        }
+        self.primary()
+    }
+
+    // Counts as a level of its own, so parentheses, blocks and call arguments nested in each other all add up.
+    fn primary(&mut self) -> Result<Expression, Error> {
+        let position = self.position();
+        self.nest(position)?;
+        let expression = self.atom(position)?;
+        self.depth -= 1;
+        Ok(expression)
+    }
+
+    fn atom(&mut self, position: usize) -> Result<Expression, Error> {
+        match self.peek().clone() {
+            Token::Integer(n) => {
+                self.advance();
+                let n = i32::try_from(n).map_err(|_| Error::new(position, "integer literal is too large for `i32`"))?;
+                Ok(Expression::Integer(n))
+            }
+            Token::Name(name) => {
+                self.advance();
+                if !self.eat(Token::Symbol('(')) {
+                    return Ok(Expression::Variable { name, position });
+                }
+                let mut arguments = Vec::new();
+                while !self.eat(Token::Symbol(')')) {
+                    if !arguments.is_empty() {
+                        self.expect(Token::Symbol(','))?;
+                    }
+                    arguments.push(self.expression()?);
+                }
+                Ok(Expression::Call { name, arguments, position })
+            }
+            Token::Symbol('(') => {
+                self.advance();
+                if self.eat(Token::Symbol(')')) {
+                    return Ok(Expression::Unit);
+                }
+                let expression = self.expression()?;
+                self.expect(Token::Symbol(')'))?;
+                Ok(expression)
+            }
+            Token::Symbol('{') => Ok(Expression::Block(self.block()?)),
+            // Token::Let lands here too: "expected expression, found `let` statement", the same words rustc uses.
+            _ => Err(self.unexpected("expression")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_trailing_expression_becomes_the_tail() {
+        let block = parse("let x = 3; x + 1").unwrap();
+        assert_eq!(block.statements.len(), 1);
+        assert!(matches!(block.tail.as_deref(), Some(Expression::Binary { operator: '+', .. })));
+
+        let block = parse("let x = 3; x + 1;").unwrap();
+        assert_eq!(block.statements.len(), 2);
+        assert!(block.tail.is_none());
+    }
+
+    #[test]
+    fn writes_signatures_back() {
+        let block = parse("fn add(a: i32, b: i32) -> i32 { a + b } fn hello() {}").unwrap();
+        let signatures: Vec<String> = block
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Function(function) => Some(function.signature()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(signatures, ["fn add(a: i32, b: i32) -> i32", "fn hello()"]);
+    }
+
+    #[test]
+    fn reports_what_was_expected() {
+        let message = |source| parse(source).unwrap_err().message;
+        assert_eq!(message("let x = (let y = 6);"), "expected expression, found `let` statement");
+        assert_eq!(message("let x = 5"), "expected `;`, found end of input");
+        assert_eq!(message("1 2"), "expected `;`, found `2`");
+        assert_eq!(message("fn f(x) {}"), "expected `:`, found `)`");
+        assert_eq!(message("fn f(x: u8) {}"), "unknown type `u8`, only i32 and () exist here");
+        assert_eq!(message("{ 1 "), "expected `}`, found end of input");
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse(&nested(MAX_NESTING - 1)).is_ok());
+        let error = parse(&nested(MAX_NESTING)).unwrap_err();
+        assert_eq!((error.position, error.message.as_str()), (MAX_NESTING, "expression nested too deeply"));
+
+        // Each of these would overflow the stack without the limit.
+        for source in ["(".repeat(100_000), "{".repeat(100_000), "-".repeat(100_000) + "1", "1 + ".repeat(100_000) + "1", "fn f() {".repeat(100_000)] {
+            assert_eq!(parse(&source).unwrap_err().message, "expression nested too deeply");
+        }
+    }
+}
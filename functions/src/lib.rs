@@ -0,0 +1,7 @@
+// A package can have a library crate next to its binary crate. The library lives in src/lib.rs and the binary in src/main.rs uses it by the package name, e.g. use functions::measurement::Measurement;. Other binaries of the package can use it the same way.
+
+pub mod interpreter;
+pub mod measurement;
+pub mod overflow;
+pub mod ratio;
+pub mod trace;
@@ -1,36 +1,131 @@
+// Without arguments the program runs the lesson from top to bottom. Single functions can be called by name:
+//
+//   cargo run -- list
+//   cargo run -- run print_labeled_measurement 5 h
+//   cargo run -- run plus_one 41
+//
+// The lesson functions are defined inside traced!, see src/trace.rs. Run with FUNCTIONS_TRACE=1 and every call to them is printed, so you can watch call_five call five:
+//
+//   FUNCTIONS_TRACE=1 cargo run -- run call_five
+
+mod registry;
+
+use std::env;
+use std::process;
+
+use functions::interpreter::Interpreter;
+use functions::measurement::{Measurement, Unit};
+use functions::traced;
+use registry::{Entry, Kind};
+
+const USAGE: &str = "usage: functions [list | run NAME [ARGUMENTS...]]";
+
+// Every function of the lesson that can be called on its own. The closures unpack the arguments, which Entry::call has already checked against the parameters, and return Err instead of letting the function panic.
+const REGISTRY: &[Entry] = &[
+    Entry { name: "another_function", parameters: &[], returns: None, call: |_| { another_function(); Ok(None) } },
+    Entry { name: "another_function_with_parameters", parameters: &[("x", Kind::I32)], returns: None, call: |a| { another_function_with_parameters(a[0].i32()); Ok(None) } },
+    Entry {
+        name: "print_labeled_measurement",
+        parameters: &[("value", Kind::F64), ("unit", Kind::Unit)],
+        returns: None,
+        call: |a| { print_labeled_measurement(a[0].f64(), a[1].unit()); Ok(None) },
+    },
+    Entry { name: "statements_and_expressions", parameters: &[], returns: None, call: |_| { statements_and_expressions(); Ok(None) } },
+    Entry { name: "five", parameters: &[], returns: Some(Kind::I32), call: |_| Ok(Some(five().to_string())) },
+    Entry { name: "call_five", parameters: &[], returns: None, call: |_| { call_five(); Ok(None) } },
+    // plus_one itself would panic at i32::MAX in a debug build, so the argument is checked first and the overflow reported like any other error.
+    Entry {
+        name: "plus_one",
+        parameters: &[("x", Kind::I32)],
+        returns: Some(Kind::I32),
+        call: |a| match a[0].i32().checked_add(1) {
+            Some(_) => Ok(Some(plus_one(a[0].i32()).to_string())),
+            None => Err(format!("{} + 1 does not fit in an i32", a[0].i32())),
+        },
+    },
+];
+
 // The main function is the entry point of Rust programs.
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => lesson(),
+        Some("list") if args.len() == 1 => {
+            for entry in REGISTRY {
+                println!("{}", entry.signature());
+            }
+        }
+        Some("run") if args.len() >= 2 => {
+            match registry::find(REGISTRY, &args[1]).and_then(|entry| entry.call(&args[2..])) {
+                Ok(Some(value)) => println!("=> {value}"),
+                Ok(None) => {}
+                Err(error) => {
+                    eprintln!("{error}");
+                    process::exit(2);
+                }
+            }
+        }
+        Some(_) => {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    }
+}
+
+fn lesson() {
     println!("Hello, world!");
 
-    // Rust doesn’t care where you define your functions, only that they’re defined somewhere in a scope that can be seen by the caller. The below function can be called above in main().
+    // Rust doesn’t care where you define your functions, only that they’re defined somewhere in a scope that can be seen by the caller. The below function can be called above in lesson().
     another_function();
 
     // Call function with argument 5 for x.
     another_function_with_parameters(5);
 
-    print_labeled_measurement(5, 'h')
+    print_labeled_measurement(5.0, Unit::HOUR);
+
+    // parse works for any type that implements FromStr, Measurement included.
+    let distance: Measurement = "12.5 km".parse().expect("a valid measurement");
+    println!("The distance is: {distance}");
+    match distance + Measurement::new(3.0, Unit::HOUR) {
+        Ok(sum) => println!("The sum is: {sum}"),
+        Err(error) => println!("Adding 3 h to it fails: {error}"),
+    }
+
+    statements_and_expressions();
+
+    call_five();
+
+    let x = plus_one(5);
+    println!("The value of x is: {x}");
 }
 
 // fn keyword allows you to define new functions.
 
 // Rust code uses snake case as the conventional style for function and variable names, in which all letters are lowercase and underscores separate words.
 
-fn another_function() {
-    println!("Another function.");
+traced! {
+    fn another_function() {
+        println!("Another function.");
+    }
 }
 
 // We can define functions to have parameters, which are special variables that are part of a function’s signature. When a function has parameters, you can provide it with concrete values for those parameters. Technically, the concrete values are called arguments, but in casual conversation, people tend to use the words parameter and argument interchangeably.
 
 // In function signatures, you must declare the type of each parameter.
 
-fn another_function_with_parameters(x: i32) {
-    println!("The value of x is: {x}");
+traced! {
+    fn another_function_with_parameters(x: i32) {
+        println!("The value of x is: {x}");
+    }
 }
 
-// Function with multiple parameters.
+// Function with multiple parameters. It used to take the unit as a char, print_labeled_measurement(value: i32, unit_label: char), but then any char is accepted as a unit. The Unit enum only allows the units that exist.
 
-fn print_labeled_measurement(value: i32, unit_label: char) {
-    println!("The measurement is: {value}{unit_label}");
+traced! {
+    fn print_labeled_measurement(value: f64, unit: Unit) {
+        let measurement = Measurement::new(value, unit);
+        println!("The measurement is: {measurement}");
+    }
 }
 
 // Statements and Expressions
@@ -41,43 +136,62 @@ fn print_labeled_measurement(value: i32, unit_label: char) {
 
 // Expressions evaluate to a resultant value.
 
-fn statements_and_expressions() {
-    // Creating a variable and assigning it a value is a statement.
-    let y = 6;
+traced! {
+    fn statements_and_expressions() {
+        // Creating a variable and assigning it a value is a statement.
+        let y = 6;
+        println!("The value of y is: {y}");
 
-    // Function definitions are also statements.
+        // Function definitions are also statements.
 
-    // Statements do not return values, therefore, you can't assign a let statement to another variable, like below:
-    let x = (let y = 6); // The let y = 6 statement does not return a value, so there isn’t anything for x to bind to. In other languages, like C or Ruby, an assignment returns the value of the assignment, so you can write, e.g., x = y = 6.
+        // Statements do not return values, therefore, you can't assign a let statement to another variable, like let x = (let y = 6);. That line does not compile, so it lives in tests/compile_fail/let_as_expression.rs, where `cargo test` checks that rustc still rejects it.
 
-    // Expressions evaluate to a value, e.g., 5 + 6 evaluates to 11.
+        // Expressions evaluate to a value, e.g., 5 + 6 evaluates to 11.
 
-    // Calling a function is an expressions. Calling a macro is an expression.A new scope block created with curly brackets is an expression.
+        // Calling a function is an expressions. Calling a macro is an expression.A new scope block created with curly brackets is an expression.
 
-    // This expression evaluates to 4, which is assigned to y. Note that the x + 1 line doesn’t have a semicolon at the end. Expressions do not include ending semicolons. If you add a semicolon to the end of an expression, you turn it into a statement, and it will then not return a value.
-    let y = {
-        let x = 3;
-        x + 1
-    };
+        // This expression evaluates to 4, which is assigned to y. Note that the x + 1 line doesn’t have a semicolon at the end. Expressions do not include ending semicolons. If you add a semicolon to the end of an expression, you turn it into a statement, and it will then not return a value.
+        let y = {
+            let x = 3;
+            x + 1
+        };
+
+        println!("The value of y is: {y}");
 
-    println!("The value of y is: {y}");
+        // src/interpreter.rs follows the same rules for a small part of Rust, so the examples above can be tried out as text, including the one that does not compile.
+        let mut interpreter = Interpreter::new();
+        for source in ["let y = { let x = 3; x + 1 }; y", "{ let x = 3; x + 1; }", "let x = (let y = 6);"] {
+            match interpreter.run(source) {
+                Ok(value) => println!("{source}  evaluates to {value}"),
+                Err(error) => println!("{source}  fails: {}", error.message),
+            }
+        }
+    }
 }
 
 // Functions with Return Values:
 
 // We don’t name return values, but we must declare their type after an arrow (->). In Rust, the return value of the function is synonymous with the value of the final expression in the block of the body of a function. You can return early from a function by using the return keyword and specifying a value, but most functions return the last expression implicitly.
 
-fn five() -> i32 {
-    5
+traced! {
+    fn five() -> i32 {
+        5
+    }
 }
 
-fn call_five() {
-    let x = five();
+traced! {
+    fn call_five() {
+        let x = five();
 
-    println!("The value of x is: {x}");
+        println!("The value of x is: {x}");
+    }
 }
 
-// The function below would cause a compilation error, since it evaluates the return line as a statement, not an expression, which doesn't return anything, as required by the function signature:
-fn plus_one(x: i32) -> i32 {
-    x + 1;
+// plus_one panics at i32::MAX in a debug build and wraps around in a release build. src/overflow.rs has versions for every integer type that choose what happens instead: cargo run --bin overflow.
+
+// With a semicolon after x + 1 the function below would cause a compilation error, since the return line would be a statement, not an expression, which doesn't return anything, as required by the function signature. That version is in tests/compile_fail/plus_one_semicolon.rs.
+traced! {
+    fn plus_one(x: i32) -> i32 {
+        x + 1
+    }
 }
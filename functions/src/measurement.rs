@@ -0,0 +1,415 @@
+// print_labeled_measurement(value: i32, unit_label: char) takes any char as a unit, so nothing stops 'x', and "m" could mean meters or minutes. A Measurement keeps the value together with a Unit, which can only be one of the units in the table below.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+
+use crate::ratio::Ratio;
+
+// What a unit measures. Only units of the same dimension can be converted into each other or added up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Temperature,
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Time => "time",
+            Dimension::Temperature => "temperature",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// The units the others are made from. Meters, grams, seconds and kelvin also take SI prefixes, see PREFIXES.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    Meter,
+    Inch,
+    Foot,
+    Yard,
+    Mile,
+    Gram,
+    Ounce,
+    Pound,
+    Stone,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+// Every dimension has one unit the others are measured against: meters, grams, seconds and kelvin. A value in another unit becomes a value in that one with (value + offset) * scale. Only temperatures have an offset, because their scales start at different zeros.
+// The factors are exact fractions. The imperial units have been defined in metric units since 1959, e.g. an inch is exactly 2.54 cm.
+struct BaseInfo {
+    base: Base,
+    symbol: &'static str,
+    dimension: Dimension,
+    scale: Ratio,
+    offset: Ratio,
+    prefixable: bool,
+}
+
+const fn info(base: Base, symbol: &'static str, dimension: Dimension, scale: Ratio, offset: Ratio, prefixable: bool) -> BaseInfo {
+    BaseInfo { base, symbol, dimension, scale, offset, prefixable }
+}
+
+const BASES: [BaseInfo; 16] = [
+    info(Base::Meter, "m", Dimension::Length, Ratio::ONE, Ratio::ZERO, true),
+    info(Base::Inch, "in", Dimension::Length, Ratio::exact(127, 5000), Ratio::ZERO, false),
+    info(Base::Foot, "ft", Dimension::Length, Ratio::exact(381, 1250), Ratio::ZERO, false),
+    info(Base::Yard, "yd", Dimension::Length, Ratio::exact(1143, 1250), Ratio::ZERO, false),
+    info(Base::Mile, "mi", Dimension::Length, Ratio::exact(201168, 125), Ratio::ZERO, false),
+    info(Base::Gram, "g", Dimension::Mass, Ratio::ONE, Ratio::ZERO, true),
+    info(Base::Ounce, "oz", Dimension::Mass, Ratio::exact(45359237, 1600000), Ratio::ZERO, false),
+    info(Base::Pound, "lb", Dimension::Mass, Ratio::exact(45359237, 100000), Ratio::ZERO, false),
+    info(Base::Stone, "st", Dimension::Mass, Ratio::exact(317514659, 50000), Ratio::ZERO, false),
+    info(Base::Second, "s", Dimension::Time, Ratio::ONE, Ratio::ZERO, true),
+    info(Base::Minute, "min", Dimension::Time, Ratio::exact(60, 1), Ratio::ZERO, false),
+    info(Base::Hour, "h", Dimension::Time, Ratio::exact(3600, 1), Ratio::ZERO, false),
+    info(Base::Day, "d", Dimension::Time, Ratio::exact(86400, 1), Ratio::ZERO, false),
+    info(Base::Celsius, "°C", Dimension::Temperature, Ratio::ONE, Ratio::exact(5463, 20), false),
+    info(Base::Fahrenheit, "°F", Dimension::Temperature, Ratio::exact(5, 9), Ratio::exact(45967, 100), false),
+    info(Base::Kelvin, "K", Dimension::Temperature, Ratio::ONE, Ratio::ZERO, true),
+];
+
+// The SI prefixes and the power of ten they stand for. u is accepted for µ, which is hard to type.
+const PREFIXES: [(&str, i32); 12] =
+    [("n", -9), ("µ", -6), ("u", -6), ("m", -3), ("c", -2), ("d", -1), ("da", 1), ("h", 2), ("k", 3), ("M", 6), ("G", 9), ("T", 12)];
+
+// A unit is a base unit with an optional SI prefix, stored as its power of ten: the kilometer is the meter with exponent 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unit {
+    base: Base,
+    // Private, so only the exponents of real prefixes can be set, by parse and the constants.
+    exponent: i32,
+}
+
+impl Unit {
+    pub const MILLIMETER: Unit = Unit::prefixed(Base::Meter, -3);
+    pub const CENTIMETER: Unit = Unit::prefixed(Base::Meter, -2);
+    pub const METER: Unit = Unit::new(Base::Meter);
+    pub const KILOMETER: Unit = Unit::prefixed(Base::Meter, 3);
+    pub const MILLIGRAM: Unit = Unit::prefixed(Base::Gram, -3);
+    pub const GRAM: Unit = Unit::new(Base::Gram);
+    pub const KILOGRAM: Unit = Unit::prefixed(Base::Gram, 3);
+    pub const MILLISECOND: Unit = Unit::prefixed(Base::Second, -3);
+    pub const SECOND: Unit = Unit::new(Base::Second);
+    pub const MINUTE: Unit = Unit::new(Base::Minute);
+    pub const HOUR: Unit = Unit::new(Base::Hour);
+    pub const CELSIUS: Unit = Unit::new(Base::Celsius);
+    pub const FAHRENHEIT: Unit = Unit::new(Base::Fahrenheit);
+    pub const KELVIN: Unit = Unit::new(Base::Kelvin);
+
+    pub const fn new(base: Base) -> Unit {
+        Unit { base, exponent: 0 }
+    }
+
+    const fn prefixed(base: Base, exponent: i32) -> Unit {
+        Unit { base, exponent }
+    }
+
+    fn info(self) -> &'static BaseInfo {
+        BASES.iter().find(|info| info.base == self.base).expect("every base unit is in BASES")
+    }
+
+    pub fn symbol(self) -> String {
+        let prefix = PREFIXES.iter().find(|&&(_, exponent)| exponent == self.exponent).map_or("", |(symbol, _)| symbol);
+        format!("{prefix}{}", self.info().symbol)
+    }
+
+    pub fn dimension(self) -> Dimension {
+        self.info().dimension
+    }
+
+    // How many base units one of this unit is, prefix included. None only if the power of ten does not fit.
+    pub fn scale(self) -> Option<Ratio> {
+        self.info().scale.checked_mul(Ratio::power_of_ten(self.exponent)?)
+    }
+
+    pub fn offset(self) -> Ratio {
+        self.info().offset
+    }
+
+    // Whole symbols are tried first, so "min" is minutes and "mi" miles rather than a milli-something. Then a prefix followed by a base unit that takes prefixes, as in "km" or "ms". The degree sign is optional, so "100F" works as well as "100 °F".
+    pub fn parse(symbol: &str) -> Option<Unit> {
+        let symbol = match symbol {
+            "C" => "°C",
+            "F" => "°F",
+            other => other,
+        };
+        if let Some(info) = BASES.iter().find(|info| info.symbol == symbol) {
+            return Some(Unit::new(info.base));
+        }
+        PREFIXES.iter().find_map(|&(prefix, exponent)| {
+            let rest = symbol.strip_prefix(prefix)?;
+            let info = BASES.iter().find(|info| info.prefixable && info.symbol == rest)?;
+            Some(Unit::prefixed(info.base, exponent))
+        })
+    }
+
+    // The units this one can be converted into, for error messages. The unit itself is left out, and so are the examples that would repeat it.
+    pub fn compatible(self) -> String {
+        let dimension = self.dimension();
+        let units: Vec<String> = BASES
+            .iter()
+            .filter(|info| info.dimension == dimension && (info.prefixable || Unit::new(info.base) != self))
+            .map(|info| {
+                if !info.prefixable {
+                    return info.symbol.to_string();
+                }
+                let examples: Vec<String> =
+                    [3, -3, -6].into_iter().map(|exponent| Unit::prefixed(info.base, exponent)).filter(|&unit| unit != self).take(2).map(Unit::symbol).collect();
+                let examples = examples.join(" or ");
+                if Unit::new(info.base) == self {
+                    format!("{} with an SI prefix (e.g. {examples})", info.symbol)
+                } else if info.base == self.base {
+                    format!("{} (also with other SI prefixes, e.g. {examples})", info.symbol)
+                } else {
+                    format!("{} (also with SI prefixes, e.g. {examples})", info.symbol)
+                }
+            })
+            .collect();
+        units.join(", ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeasurementError {
+    MissingNumber(String),
+    UnknownUnit(String),
+    Incompatible { from: Unit, to: Unit },
+    // The exact result needs more digits than an i128 holds.
+    Overflow,
+}
+
+impl fmt::Display for MeasurementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MeasurementError::MissingNumber(text) => write!(f, "`{text}` does not start with a number"),
+            MeasurementError::UnknownUnit(symbol) => write!(f, "unknown unit `{symbol}`"),
+            MeasurementError::Incompatible { from, to } => write!(
+                f,
+                "{} measures {} and {} measures {}. {} only converts to {}",
+                from.symbol(),
+                from.dimension(),
+                to.symbol(),
+                to.dimension(),
+                from.symbol(),
+                from.compatible()
+            ),
+            MeasurementError::Overflow => write!(f, "the exact result is too large"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Measurement {
+    pub fn new(value: f64, unit: Unit) -> Measurement {
+        Measurement { value, unit }
+    }
+
+    // Returns an error instead of a wrong number when the units measure different things.
+    pub fn convert_to(self, unit: Unit) -> Result<Measurement, MeasurementError> {
+        if self.unit.dimension() != unit.dimension() {
+            return Err(MeasurementError::Incompatible { from: self.unit, to: unit });
+        }
+        let (from, to) = (self.unit, unit);
+        let scale = |unit: Unit| unit.scale().map_or(f64::NAN, Ratio::to_f64);
+        let base = (self.value + from.offset().to_f64()) * scale(from);
+        Ok(Measurement::new(base / scale(to) - to.offset().to_f64(), to))
+    }
+
+    // rhs in the unit of self. For temperatures rhs counts as a difference: 20 °C + 9 °F is 25 °C, a rise of 9 °F being a rise of 5 °C. That is why only the scale is used here, never the offset.
+    fn difference_in(self, rhs: Measurement) -> Result<f64, MeasurementError> {
+        if self.unit.dimension() != rhs.unit.dimension() {
+            return Err(MeasurementError::Incompatible { from: self.unit, to: rhs.unit });
+        }
+        let (scale, rhs_scale) = (self.unit.scale().ok_or(MeasurementError::Overflow)?, rhs.unit.scale().ok_or(MeasurementError::Overflow)?);
+        Ok(rhs.value * rhs_scale.to_f64() / scale.to_f64())
+    }
+}
+
+// The same conversion as convert_to, but with exact fractions. This is what the convert binary uses.
+pub fn convert_exact(value: Ratio, from: Unit, to: Unit) -> Result<Ratio, MeasurementError> {
+    if from.dimension() != to.dimension() {
+        return Err(MeasurementError::Incompatible { from, to });
+    }
+    // and_then chains steps that can each fail. The first None ends the chain.
+    let base = value.checked_add(from.offset()).and_then(|v| v.checked_mul(from.scale()?));
+    base.and_then(|v| v.checked_div(to.scale()?)).and_then(|v| v.checked_sub(to.offset())).ok_or(MeasurementError::Overflow)
+}
+
+// Splits "12.5 km" into the number and the unit, without parsing the number. The number is everything up to the first character that cannot be part of one.
+pub fn split(text: &str) -> Result<(&str, Unit), MeasurementError> {
+    let text = text.trim();
+    let at = text.find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+'))).unwrap_or(text.len());
+    let (number, symbol) = text.split_at(at);
+    if number.is_empty() {
+        return Err(MeasurementError::MissingNumber(text.to_string()));
+    }
+    let symbol = symbol.trim();
+    match Unit::parse(symbol) {
+        Some(unit) => Ok((number, unit)),
+        None => Err(MeasurementError::UnknownUnit(symbol.to_string())),
+    }
+}
+
+// FromStr is the trait behind str::parse, so "12.5 km".parse::<Measurement>() works.
+impl FromStr for Measurement {
+    type Err = MeasurementError;
+
+    fn from_str(text: &str) -> Result<Measurement, MeasurementError> {
+        let (number, unit) = split(text)?;
+        let value = number.parse().map_err(|_| MeasurementError::MissingNumber(text.trim().to_string()))?;
+        Ok(Measurement::new(value, unit))
+    }
+}
+
+// A precision like {:.1} is passed on to the value.
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} {}", precision, self.value, self.unit.symbol()),
+            None => write!(f, "{} {}", self.value, self.unit.symbol()),
+        }
+    }
+}
+
+// Implementing the operator traits makes + and - work on measurements. Adding can fail, so the Output is a Result: 5 m + 3 s is an error, not 8 of something. The result is in the unit of the left side.
+impl Add for Measurement {
+    type Output = Result<Measurement, MeasurementError>;
+
+    fn add(self, rhs: Measurement) -> Self::Output {
+        Ok(Measurement::new(self.value + self.difference_in(rhs)?, self.unit))
+    }
+}
+
+impl Sub for Measurement {
+    type Output = Result<Measurement, MeasurementError>;
+
+    fn sub(self, rhs: Measurement) -> Self::Output {
+        Ok(Measurement::new(self.value - self.difference_in(rhs)?, self.unit))
+    }
+}
+
+// Scaling by a plain number always works.
+impl Mul<f64> for Measurement {
+    type Output = Measurement;
+
+    fn mul(self, factor: f64) -> Measurement {
+        Measurement::new(self.value * factor, self.unit)
+    }
+}
+
+impl Div<f64> for Measurement {
+    type Output = Measurement;
+
+    fn div(self, divisor: f64) -> Measurement {
+        Measurement::new(self.value / divisor, self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Measurement {
+        text.parse().unwrap()
+    }
+
+    fn close(a: Measurement, b: Measurement) -> bool {
+        a.unit == b.unit && (a.value - b.value).abs() < 1e-9
+    }
+
+    #[test]
+    fn parses_with_and_without_a_space() {
+        assert_eq!(parse("5h"), Measurement::new(5.0, Unit::HOUR));
+        assert_eq!(parse(" 12.5 km "), Measurement::new(12.5, Unit::KILOMETER));
+        assert_eq!(parse("-40F"), Measurement::new(-40.0, Unit::FAHRENHEIT));
+        assert_eq!(parse("3 min"), Measurement::new(3.0, Unit::MINUTE));
+        assert_eq!(parse("3m"), Measurement::new(3.0, Unit::METER));
+        assert_eq!("km".parse::<Measurement>(), Err(MeasurementError::MissingNumber("km".to_string())));
+        assert_eq!("5 parsecs".parse::<Measurement>(), Err(MeasurementError::UnknownUnit("parsecs".to_string())));
+    }
+
+    #[test]
+    fn displays_value_and_symbol() {
+        assert_eq!(parse("12.5km").to_string(), "12.5 km");
+        assert_eq!(format!("{:.1}", parse("100 C")), "100.0 °C");
+    }
+
+    #[test]
+    fn converts_within_a_dimension() {
+        assert!(close(parse("5h").convert_to(Unit::MINUTE).unwrap(), parse("300 min")));
+        assert!(close(parse("100 C").convert_to(Unit::FAHRENHEIT).unwrap(), parse("212 F")));
+        assert!(close(parse("-40 C").convert_to(Unit::FAHRENHEIT).unwrap(), parse("-40 F")));
+        assert!(close(parse("0 K").convert_to(Unit::CELSIUS).unwrap(), parse("-273.15 C")));
+        assert_eq!(
+            parse("1 kg").convert_to(Unit::METER),
+            Err(MeasurementError::Incompatible { from: Unit::KILOGRAM, to: Unit::METER })
+        );
+    }
+
+    #[test]
+    fn parses_prefixes_and_imperial_units() {
+        assert_eq!(Unit::parse("km"), Some(Unit::KILOMETER));
+        assert_eq!(Unit::parse("kg"), Some(Unit::KILOGRAM));
+        assert_eq!(Unit::parse("min"), Some(Unit::MINUTE));
+        assert_eq!(Unit::parse("us").map(Unit::symbol).as_deref(), Some("µs"));
+        assert_eq!(Unit::parse("dam").map(Unit::symbol).as_deref(), Some("dam"));
+        assert_eq!(Unit::parse("mi").map(Unit::dimension), Some(Dimension::Length));
+        // Hours take no prefixes.
+        assert_eq!(Unit::parse("kh"), None);
+    }
+
+    #[test]
+    fn converts_exactly() {
+        let exact = |value: &str, from: &str, to: &str| {
+            let (from, to) = (Unit::parse(from).unwrap(), Unit::parse(to).unwrap());
+            convert_exact(Ratio::parse(value).unwrap(), from, to).unwrap().to_string()
+        };
+        assert_eq!(exact("5", "h", "min"), "300");
+        assert_eq!(exact("1", "mi", "km"), "25146/15625");
+        assert_eq!(exact("12", "km", "mi"), "31250/4191");
+        assert_eq!(exact("1", "ft", "in"), "12");
+        assert_eq!(exact("1", "st", "lb"), "14");
+        assert_eq!(exact("100", "F", "C"), "340/9");
+        assert_eq!(exact("-40", "C", "F"), "-40");
+        assert_eq!(exact("0", "K", "F"), "-45967/100");
+        assert_eq!(exact("1500", "mg", "g"), "3/2");
+    }
+
+    #[test]
+    fn lists_compatible_units_when_converting_fails() {
+        let error = parse("5 kg").convert_to(Unit::METER).unwrap_err();
+        assert_eq!(error.to_string(), "kg measures mass and m measures length. kg only converts to g (also with other SI prefixes, e.g. mg or µg), oz, lb, st");
+        assert_eq!(Unit::KILOMETER.compatible(), "m (also with other SI prefixes, e.g. mm or µm), in, ft, yd, mi");
+        assert_eq!(Unit::METER.compatible(), "m with an SI prefix (e.g. km or mm), in, ft, yd, mi");
+        assert_eq!(Unit::parse("mi").unwrap().compatible(), "m (also with SI prefixes, e.g. km or mm), in, ft, yd");
+    }
+
+    #[test]
+    fn refuses_to_add_incompatible_units() {
+        assert!(close((parse("1 km") + parse("250 m")).unwrap(), parse("1.25 km")));
+        assert!(close((parse("1 h") - parse("90 min")).unwrap(), parse("-0.5 h")));
+        assert!(close((parse("20 C") + parse("9 F")).unwrap(), parse("25 C")));
+        assert!((parse("5 m") + parse("3 s")).is_err());
+        assert_eq!(parse("2 kg") * 3.0, parse("6 kg"));
+        assert_eq!(parse("6 kg") / 3.0, parse("2 kg"));
+    }
+}
@@ -0,0 +1,214 @@
+// What plus_one(x: i32) -> i32 does at i32::MAX depends on the build: a debug build panics, a release build wraps around to i32::MIN. Code that can reach the limit should say which behaviour it wants. Every integer type has four families of methods for that, and here they are behind one generic API:
+//
+//   checked      Option: None when the result does not fit         127i8.checked_add(1) is None
+//   wrapping     wraps around, like a release build                 127i8.wrapping_add(1) is -128
+//   saturating   stops at the limit                                 127i8.saturating_add(1) is 127
+//   overflowing  the wrapped result and whether it wrapped          127i8.overflowing_add(1) is (-128, true)
+//
+// cargo run --bin overflow shows all of them at the limits of every integer type.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    Checked,
+    Wrapping,
+    Saturating,
+    Overflowing,
+}
+
+impl Policy {
+    pub const ALL: [Policy; 4] = [Policy::Checked, Policy::Wrapping, Policy::Saturating, Policy::Overflowing];
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Policy::Checked => "checked",
+            Policy::Wrapping => "wrapping",
+            Policy::Saturating => "saturating",
+            Policy::Overflowing => "overflowing",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    // Division overflows too, but only in one case: MIN / -1 of a signed type, whose result is MAX + 1. Dividing by zero is not an overflow, see apply.
+    Div,
+}
+
+// The integer types have no common trait in the standard library, their methods are defined one type at a time. This trait collects the ones needed here, so code can be written once for all of them.
+pub trait Integer: Copy + PartialEq + fmt::Debug + fmt::Display {
+    const NAME: &'static str;
+    const MIN: Self;
+    const MAX: Self;
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    fn checked(self, op: Op, other: Self) -> Option<Self>;
+    fn wrapping(self, op: Op, other: Self) -> Self;
+    fn saturating(self, op: Op, other: Self) -> Self;
+    fn overflowing(self, op: Op, other: Self) -> (Self, bool);
+}
+
+// The implementations only differ in the type name, so a macro writes them. Each method forwards to the inherent method of the type, e.g. u8::checked_add.
+macro_rules! integer {
+    ($($type:ident),*) => {
+        $(
+            impl Integer for $type {
+                const NAME: &'static str = stringify!($type);
+                const MIN: $type = $type::MIN;
+                const MAX: $type = $type::MAX;
+                const ZERO: $type = 0;
+                const ONE: $type = 1;
+                const TWO: $type = 2;
+
+                fn checked(self, op: Op, other: $type) -> Option<$type> {
+                    match op {
+                        Op::Add => self.checked_add(other),
+                        Op::Sub => self.checked_sub(other),
+                        Op::Mul => self.checked_mul(other),
+                        Op::Div => self.checked_div(other),
+                    }
+                }
+
+                fn wrapping(self, op: Op, other: $type) -> $type {
+                    match op {
+                        Op::Add => self.wrapping_add(other),
+                        Op::Sub => self.wrapping_sub(other),
+                        Op::Mul => self.wrapping_mul(other),
+                        Op::Div => self.wrapping_div(other),
+                    }
+                }
+
+                fn saturating(self, op: Op, other: $type) -> $type {
+                    match op {
+                        Op::Add => self.saturating_add(other),
+                        Op::Sub => self.saturating_sub(other),
+                        Op::Mul => self.saturating_mul(other),
+                        Op::Div => self.saturating_div(other),
+                    }
+                }
+
+                fn overflowing(self, op: Op, other: $type) -> ($type, bool) {
+                    match op {
+                        Op::Add => self.overflowing_add(other),
+                        Op::Sub => self.overflowing_sub(other),
+                        Op::Mul => self.overflowing_mul(other),
+                        Op::Div => self.overflowing_div(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// The result in the shape the standard library method returns it, so it prints the same way: Some(5) or None, 5, or (5, false).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Checked(Option<T>),
+    Wrapping(T),
+    Saturating(T),
+    Overflowing(T, bool),
+}
+
+impl<T: fmt::Debug> fmt::Display for Outcome<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Formatted into a String first, so the padding of {:<12} applies to the whole text.
+        let text = match self {
+            Outcome::Checked(value) => format!("{value:?}"),
+            Outcome::Wrapping(value) | Outcome::Saturating(value) => format!("{value:?}"),
+            Outcome::Overflowing(value, overflowed) => format!("({value:?}, {overflowed})"),
+        };
+        f.pad(&text)
+    }
+}
+
+// Panics when op is Div and b is zero, except with Policy::Checked, which gives None. That is how the standard library methods behave: a result that does not fit can be wrapped or saturated, but there is no result at all to wrap for a division by zero.
+pub fn apply<T: Integer>(policy: Policy, op: Op, a: T, b: T) -> Outcome<T> {
+    match policy {
+        Policy::Checked => Outcome::Checked(a.checked(op, b)),
+        Policy::Wrapping => Outcome::Wrapping(a.wrapping(op, b)),
+        Policy::Saturating => Outcome::Saturating(a.saturating(op, b)),
+        Policy::Overflowing => {
+            let (value, overflowed) = a.overflowing(op, b);
+            Outcome::Overflowing(value, overflowed)
+        }
+    }
+}
+
+pub fn plus_one<T: Integer>(x: T, policy: Policy) -> Outcome<T> {
+    apply(policy, Op::Add, x, T::ONE)
+}
+
+pub fn minus_one<T: Integer>(x: T, policy: Policy) -> Outcome<T> {
+    apply(policy, Op::Sub, x, T::ONE)
+}
+
+pub fn double<T: Integer>(x: T, policy: Policy) -> Outcome<T> {
+    apply(policy, Op::Mul, x, T::TWO)
+}
+
+// The plain x + 1 without any policy: a panic in a debug build, wrapping in a release build. cfg!(debug_assertions) tells which kind of build this is. Division by zero panics in both.
+pub fn unchecked_behaviour<T: Integer>(op: Op, a: T, b: T) -> String {
+    match a.checked(op, b) {
+        Some(value) => value.to_string(),
+        None if cfg!(debug_assertions) || (op == Op::Div && b == T::ZERO) => "panic".to_string(),
+        None => a.wrapping(op, b).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plus_one_at_the_top() {
+        assert_eq!(plus_one(126i8, Policy::Checked), Outcome::Checked(Some(127)));
+        assert_eq!(plus_one(127i8, Policy::Checked), Outcome::Checked(None));
+        assert_eq!(plus_one(127i8, Policy::Wrapping), Outcome::Wrapping(-128));
+        assert_eq!(plus_one(127i8, Policy::Saturating), Outcome::Saturating(127));
+        assert_eq!(plus_one(127i8, Policy::Overflowing), Outcome::Overflowing(-128, true));
+        assert_eq!(plus_one(u128::MAX, Policy::Wrapping), Outcome::Wrapping(0));
+    }
+
+    #[test]
+    fn minus_one_and_double_at_the_bottom() {
+        assert_eq!(minus_one(0u8, Policy::Checked), Outcome::Checked(None));
+        assert_eq!(minus_one(0u8, Policy::Wrapping), Outcome::Wrapping(255));
+        assert_eq!(minus_one(i64::MIN, Policy::Saturating), Outcome::Saturating(i64::MIN));
+        assert_eq!(double(i32::MIN, Policy::Overflowing), Outcome::Overflowing(0, true));
+        assert_eq!(double(-64i8, Policy::Checked), Outcome::Checked(Some(-128)));
+        assert_eq!(apply(Policy::Saturating, Op::Div, i16::MIN, -1), Outcome::Saturating(i16::MAX));
+    }
+
+    #[test]
+    fn prints_like_the_standard_library() {
+        assert_eq!(plus_one(5u16, Policy::Checked).to_string(), "Some(6)");
+        assert_eq!(plus_one(u16::MAX, Policy::Checked).to_string(), "None");
+        assert_eq!(plus_one(u16::MAX, Policy::Overflowing).to_string(), "(0, true)");
+        assert_eq!(format!("[{:<6}]", plus_one(1usize, Policy::Wrapping)), "[2     ]");
+        assert_eq!(unchecked_behaviour(Op::Add, 1i8, 1), "2");
+    }
+
+    #[test]
+    fn checked_division_by_zero_is_none() {
+        assert_eq!(apply(Policy::Checked, Op::Div, 7u8, 0), Outcome::Checked(None));
+        assert_eq!(apply(Policy::Checked, Op::Div, i32::MIN, 0), Outcome::Checked(None));
+        assert_eq!(unchecked_behaviour(Op::Div, 7u8, 0), "panic");
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn other_policies_panic_on_division_by_zero() {
+        apply(Policy::Wrapping, Op::Div, 7u8, 0);
+    }
+}
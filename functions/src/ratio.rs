@@ -0,0 +1,175 @@
+// A fraction of two integers. 1 inch is exactly 254/10000 m, which no f64 can hold: f64 only stores fractions whose denominator is a power of two. Keeping numerator and denominator apart keeps conversions exact.
+// The arithmetic is checked. i128 is big, but repeated multiplication can still overflow it, and then the caller gets None instead of a wrong answer.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ratio {
+    numerator: i128,
+    // Always positive, and the fraction is always in lowest terms, so equal values have equal fields.
+    denominator: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+impl Ratio {
+    pub const ZERO: Ratio = Ratio { numerator: 0, denominator: 1 };
+    pub const ONE: Ratio = Ratio { numerator: 1, denominator: 1 };
+
+    // const fn can be evaluated at compile time, so unit tables can be consts. The fraction must already be in lowest terms, new reduces it at run time.
+    pub const fn exact(numerator: i128, denominator: i128) -> Ratio {
+        Ratio { numerator, denominator }
+    }
+
+    pub fn new(numerator: i128, denominator: i128) -> Option<Ratio> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Some(Ratio { numerator: numerator / divisor, denominator: denominator / divisor })
+    }
+
+    pub fn integer(value: i128) -> Ratio {
+        Ratio { numerator: value, denominator: 1 }
+    }
+
+    // 10 to the power of exponent, which may be negative.
+    pub fn power_of_ten(exponent: i32) -> Option<Ratio> {
+        let power = 10i128.checked_pow(exponent.unsigned_abs())?;
+        if exponent >= 0 { Ratio::new(power, 1) } else { Ratio::new(1, power) }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    // Reads a decimal number like -12.375 exactly, as -12375/1000.
+    pub fn parse(text: &str) -> Option<Ratio> {
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() && fraction.is_empty() || !(whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())) {
+            return None;
+        }
+        let numerator: i128 = format!("{whole}{fraction}").parse().ok()?;
+        Ratio::new(sign * numerator, 10i128.checked_pow(fraction.len() as u32)?)
+    }
+
+    pub fn checked_add(self, other: Ratio) -> Option<Ratio> {
+        let numerator = self.numerator.checked_mul(other.denominator)?.checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        Ratio::new(numerator, self.denominator.checked_mul(other.denominator)?)
+    }
+
+    pub fn checked_sub(self, other: Ratio) -> Option<Ratio> {
+        self.checked_add(Ratio { numerator: -other.numerator, ..other })
+    }
+
+    pub fn checked_mul(self, other: Ratio) -> Option<Ratio> {
+        // Reducing crosswise first keeps the intermediate numbers small.
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        let numerator = (self.numerator / a).checked_mul(other.numerator / b)?;
+        Ratio::new(numerator, (self.denominator / b).checked_mul(other.denominator / a)?)
+    }
+
+    pub fn checked_div(self, other: Ratio) -> Option<Ratio> {
+        if other.numerator == 0 {
+            return None;
+        }
+        self.checked_mul(Ratio::new(other.denominator, other.numerator)?)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    // The exact decimal digits, if there are finitely many. That is the case when the denominator has no prime factors other than 2 and 5, e.g. 3/8 = 0.375 but not 1/3.
+    pub fn to_decimal(self) -> Option<String> {
+        let mut rest = self.denominator;
+        let mut places = 0;
+        // Multiplying by 10 removes one 2 and one 5 from the denominator. Count how many times that is needed.
+        while rest % 2 == 0 || rest % 5 == 0 {
+            rest /= if rest % 10 == 0 { 10 } else if rest % 2 == 0 { 2 } else { 5 };
+            places += 1;
+        }
+        if rest != 1 {
+            return None;
+        }
+        let scaled = self.numerator.checked_mul(10i128.checked_pow(places)?)? / self.denominator;
+        let digits = scaled.unsigned_abs().to_string();
+        let sign = if self.numerator < 0 { "-" } else { "" };
+        let places = places as usize;
+        if places == 0 {
+            return Some(format!("{sign}{digits}"));
+        }
+        let digits = format!("{digits:0>width$}", width = places + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - places);
+        Some(format!("{sign}{whole}.{}", fraction.trim_end_matches('0')).trim_end_matches('.').to_string())
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(numerator: i128, denominator: i128) -> Ratio {
+        Ratio::new(numerator, denominator).unwrap()
+    }
+
+    #[test]
+    fn keeps_fractions_in_lowest_terms() {
+        assert_eq!(ratio(254, 10000), ratio(127, 5000));
+        assert_eq!(ratio(3, -6), ratio(-1, 2));
+        assert_eq!(ratio(-3, -6).to_string(), "1/2");
+        assert_eq!(Ratio::new(1, 0), None);
+    }
+
+    #[test]
+    fn parses_decimals_exactly() {
+        assert_eq!(Ratio::parse("12.5"), Some(ratio(25, 2)));
+        assert_eq!(Ratio::parse("-0.001"), Some(ratio(-1, 1000)));
+        assert_eq!(Ratio::parse(".5"), Some(ratio(1, 2)));
+        assert_eq!(Ratio::parse("7."), Some(ratio(7, 1)));
+        assert_eq!(Ratio::parse("."), None);
+        assert_eq!(Ratio::parse("1e3"), None);
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let (a, b) = (ratio(1, 3), ratio(1, 6));
+        assert_eq!(a.checked_add(b), Some(ratio(1, 2)));
+        assert_eq!(a.checked_sub(b), Some(ratio(1, 6)));
+        assert_eq!(a.checked_mul(b), Some(ratio(1, 18)));
+        assert_eq!(a.checked_div(b), Some(ratio(2, 1)));
+        assert_eq!(a.checked_div(Ratio::ZERO), None);
+        assert_eq!(Ratio::integer(i128::MAX).checked_mul(Ratio::integer(2)), None);
+        assert_eq!(Ratio::power_of_ten(-3), Some(ratio(1, 1000)));
+    }
+
+    #[test]
+    fn writes_terminating_decimals() {
+        assert_eq!(ratio(3, 8).to_decimal().as_deref(), Some("0.375"));
+        assert_eq!(ratio(-25, 2).to_decimal().as_deref(), Some("-12.5"));
+        assert_eq!(ratio(300, 1).to_decimal().as_deref(), Some("300"));
+        assert_eq!(ratio(1, 20).to_decimal().as_deref(), Some("0.05"));
+        assert_eq!(ratio(1, 3).to_decimal(), None);
+    }
+}
@@ -0,0 +1,183 @@
+// A table of the lesson's functions that can be called by name from the command line. Arguments arrive as text, so every entry lists the types of its parameters, and the text is parsed into those types before the function runs.
+//
+// A Rust program cannot look up its own functions by name at run time, there is no reflection. The table is written by hand instead, in main.rs next to the functions.
+
+use std::fmt;
+
+use functions::measurement::Unit;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    I32,
+    F64,
+    Unit,
+}
+
+impl Kind {
+    fn example(self) -> &'static str {
+        match self {
+            Kind::I32 => "a whole number like 5 or -3",
+            Kind::F64 => "a number like 5 or 2.5",
+            Kind::Unit => "a unit like km, h or F",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::I32 => write!(f, "i32"),
+            Kind::F64 => write!(f, "f64"),
+            Kind::Unit => write!(f, "Unit"),
+        }
+    }
+}
+
+// An argument after parsing. The accessors are only called after Entry::call has checked the types against the parameters, so a wrong type there is a mistake in the table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Argument {
+    I32(i32),
+    F64(f64),
+    Unit(Unit),
+}
+
+impl Argument {
+    fn parse(kind: Kind, text: &str) -> Option<Argument> {
+        match kind {
+            Kind::I32 => text.parse().ok().map(Argument::I32),
+            Kind::F64 => text.parse().ok().filter(|value: &f64| value.is_finite()).map(Argument::F64),
+            Kind::Unit => Unit::parse(text).map(Argument::Unit),
+        }
+    }
+
+    pub fn i32(self) -> i32 {
+        match self {
+            Argument::I32(value) => value,
+            other => panic!("expected an i32 argument, the table says {other:?}"),
+        }
+    }
+
+    pub fn f64(self) -> f64 {
+        match self {
+            Argument::F64(value) => value,
+            other => panic!("expected an f64 argument, the table says {other:?}"),
+        }
+    }
+
+    pub fn unit(self) -> Unit {
+        match self {
+            Argument::Unit(unit) => unit,
+            other => panic!("expected a Unit argument, the table says {other:?}"),
+        }
+    }
+}
+
+pub struct Entry {
+    pub name: &'static str,
+    pub parameters: &'static [(&'static str, Kind)],
+    // None for functions that return ().
+    pub returns: Option<Kind>,
+    // A plain fn pointer. Closures that capture nothing can be turned into one, so the table can be a const. Err holds why the call could not be made with these arguments.
+    pub call: fn(&[Argument]) -> Result<Option<String>, String>,
+}
+
+impl Entry {
+    pub fn signature(&self) -> String {
+        let parameters: Vec<String> = self.parameters.iter().map(|(name, kind)| format!("{name}: {kind}")).collect();
+        let returns = self.returns.map_or(String::new(), |kind| format!(" -> {kind}"));
+        format!("fn {}({}){returns}", self.name, parameters.join(", "))
+    }
+
+    // Parses the arguments and calls the function. Returns its value as text, or None for ().
+    pub fn call(&self, arguments: &[String]) -> Result<Option<String>, CallError> {
+        if arguments.len() != self.parameters.len() {
+            return Err(CallError::Arity { signature: self.signature(), expected: self.parameters.len(), given: arguments.len() });
+        }
+        let mut parsed = Vec::new();
+        for (&(parameter, kind), text) in self.parameters.iter().zip(arguments) {
+            let argument = Argument::parse(kind, text).ok_or_else(|| CallError::Type { signature: self.signature(), parameter, kind, found: text.clone() })?;
+            parsed.push(argument);
+        }
+        (self.call)(&parsed).map_err(|message| CallError::Failed { signature: self.signature(), message })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CallError {
+    Unknown { name: String, similar: Vec<&'static str> },
+    Arity { signature: String, expected: usize, given: usize },
+    Type { signature: String, parameter: &'static str, kind: Kind, found: String },
+    // The arguments have the right types, but the function cannot handle them, e.g. plus_one(2147483647).
+    Failed { signature: String, message: String },
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        match self {
+            CallError::Unknown { name, similar } if similar.is_empty() => write!(f, "there is no function `{name}`, `functions list` shows them all"),
+            CallError::Unknown { name, similar } => write!(f, "there is no function `{name}`, did you mean {}?", similar.join(" or ")),
+            CallError::Arity { signature, expected, given } => {
+                write!(f, "{signature} takes {expected} argument{} but {given} {} given", plural(*expected), if *given == 1 { "was" } else { "were" })
+            }
+            CallError::Type { signature, parameter, kind, found } => {
+                write!(f, "`{found}` is not a valid {kind} for `{parameter}` in {signature}, expected {}", kind.example())
+            }
+            CallError::Failed { signature, message } => write!(f, "{signature} failed: {message}"),
+        }
+    }
+}
+
+// Names that contain what was typed, or are contained in it, are offered as suggestions: "five" finds five and call_five.
+pub fn find<'a>(registry: &'a [Entry], name: &str) -> Result<&'a Entry, CallError> {
+    if let Some(entry) = registry.iter().find(|entry| entry.name == name) {
+        return Ok(entry);
+    }
+    let similar = registry.iter().filter(|entry| !name.is_empty() && (entry.name.contains(name) || name.contains(entry.name))).map(|entry| entry.name).collect();
+    Err(CallError::Unknown { name: name.to_string(), similar })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &[Entry] = &[
+        Entry { name: "double", parameters: &[("x", Kind::I32)], returns: Some(Kind::I32), call: |a| a[0].i32().checked_mul(2).map(|x| Some(x.to_string())).ok_or_else(|| "too large".to_string()) },
+        Entry { name: "show", parameters: &[("value", Kind::F64), ("unit", Kind::Unit)], returns: None, call: |_| Ok(None) },
+        Entry { name: "double_show", parameters: &[], returns: None, call: |_| Ok(None) },
+    ];
+
+    fn call(name: &str, arguments: &[&str]) -> Result<Option<String>, CallError> {
+        let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+        find(REGISTRY, name)?.call(&arguments)
+    }
+
+    #[test]
+    fn calls_by_name_with_parsed_arguments() {
+        assert_eq!(call("double", &["21"]), Ok(Some("42".to_string())));
+        assert_eq!(call("show", &["5", "h"]), Ok(None));
+        assert_eq!(REGISTRY[1].signature(), "fn show(value: f64, unit: Unit)");
+    }
+
+    #[test]
+    fn reports_wrong_arity_and_types() {
+        assert_eq!(call("double", &[]).unwrap_err().to_string(), "fn double(x: i32) -> i32 takes 1 argument but 0 were given");
+        assert_eq!(
+            call("double", &["2.5"]).unwrap_err().to_string(),
+            "`2.5` is not a valid i32 for `x` in fn double(x: i32) -> i32, expected a whole number like 5 or -3"
+        );
+        assert!(matches!(call("show", &["5", "parsec"]), Err(CallError::Type { parameter: "unit", .. })));
+        assert!(matches!(call("show", &["inf", "h"]), Err(CallError::Type { parameter: "value", .. })));
+    }
+
+    #[test]
+    fn reports_failed_calls() {
+        assert_eq!(call("double", &["2000000000"]).unwrap_err().to_string(), "fn double(x: i32) -> i32 failed: too large");
+    }
+
+    #[test]
+    fn suggests_similar_names() {
+        assert_eq!(call("doubl", &[]).unwrap_err(), CallError::Unknown { name: "doubl".to_string(), similar: vec!["double", "double_show"] });
+        assert_eq!(call("triple", &[]).unwrap_err().to_string(), "there is no function `triple`, `functions list` shows them all");
+    }
+}
@@ -0,0 +1,130 @@
+// Shows the calls of a program as they happen. Functions defined inside traced! print a line when they are called and another one with their return value when they return, indented by how deeply the calls are nested:
+//
+//   call_five()
+//     five()
+//     → five() = 5
+//   The value of x is: 5
+//   → call_five() = ()
+//
+// Tracing is switched on by running the program with FUNCTIONS_TRACE set, e.g. FUNCTIONS_TRACE=1 cargo run -- run call_five. The built program reads it as well, no new compile is needed. The price is that tracing is decided at run time: every call to a traced function reads a OnceLock and goes through a closure, even when nothing is printed. That is cheap, but not free. A cfg flag would remove it at compile time, at the cost of a new compile to switch.
+
+use std::cell::Cell;
+use std::env;
+use std::fmt::Debug;
+use std::sync::OnceLock;
+
+// The environment is read at the first traced call and the answer kept, so later calls do not look the variable up again. OnceLock makes that safe even if the first calls happen on several threads at once.
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| env::var_os("FUNCTIONS_TRACE").is_some())
+}
+
+thread_local! {
+    // How many traced calls are running right now on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+pub fn line(depth: usize, text: &str) -> String {
+    format!("{}{text}", "  ".repeat(depth))
+}
+
+// One traced call in progress. enter and exit print the two lines; Drop keeps the depth right even if the function panics and exit never runs.
+pub struct Call {
+    call: String,
+    depth: usize,
+}
+
+impl Call {
+    // The trace goes to stderr, so it does not mix with what the program prints when its output is piped somewhere.
+    pub fn enter(name: &str, arguments: &[&dyn Debug]) -> Call {
+        let arguments: Vec<String> = arguments.iter().map(|argument| format!("{argument:?}")).collect();
+        let call = format!("{name}({})", arguments.join(", "));
+        let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+        eprintln!("{}", line(depth, &call));
+        Call { call, depth }
+    }
+
+    pub fn exit<T: Debug>(self, value: &T) {
+        eprintln!("{}", line(self.depth, &format!("→ {} = {value:?}", self.call)));
+    }
+
+    pub fn depth() -> usize {
+        DEPTH.with(Cell::get)
+    }
+}
+
+impl Drop for Call {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(self.depth));
+    }
+}
+
+// Wraps function definitions. The functions keep their names, signatures and bodies, only the tracing is added around the body:
+//
+//   traced! {
+//       fn plus_one(x: i32) -> i32 {
+//           x + 1
+//       }
+//   }
+//
+// The arguments are formatted with {:?} before the body runs, so every parameter type has to implement Debug, and so does the return type. The body runs inside a closure, so that a return in it comes back to the tracing code instead of leaving the function. There is only the one copy of the body, traced or not.
+#[macro_export]
+macro_rules! traced {
+    ($($(#[$attribute:meta])* $visibility:vis fn $name:ident($($parameter:ident: $type:ty),* $(,)?) $(-> $returns:ty)? $body:block)*) => {
+        $(
+            $(#[$attribute])*
+            $visibility fn $name($($parameter: $type),*) $(-> $returns)? {
+                let call = $crate::trace::enabled().then(|| $crate::trace::Call::enter(stringify!($name), &[$(&$parameter),*]));
+                // Clippy would rather see the body without the closure, but then a return in it would skip call.exit.
+                #[allow(clippy::redundant_closure_call)]
+                let value = (move || $body)();
+                if let Some(call) = call {
+                    call.exit(&value);
+                }
+                value
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_by_depth() {
+        assert_eq!(line(0, "five()"), "five()");
+        assert_eq!(line(2, "→ five() = 5"), "    → five() = 5");
+    }
+
+    #[test]
+    fn tracks_how_deeply_calls_are_nested() {
+        assert_eq!(Call::depth(), 0);
+        let outer = Call::enter("call_five", &[]);
+        let inner = Call::enter("plus_one", &[&41]);
+        assert_eq!(Call::depth(), 2);
+        inner.exit(&42);
+        assert_eq!(Call::depth(), 1);
+        outer.exit(&());
+        assert_eq!(Call::depth(), 0);
+    }
+
+    traced! {
+        fn add(a: i32, b: i32) -> i32 {
+            if b == 0 {
+                return a;
+            }
+            a + b
+        }
+
+        fn nothing() {}
+    }
+
+    #[test]
+    fn traced_functions_behave_like_plain_ones() {
+        assert_eq!(add(2, 3), 5);
+        assert_eq!(add(2, 0), 2);
+        nothing();
+        assert_eq!(Call::depth(), 0);
+    }
+}
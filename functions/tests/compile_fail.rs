@@ -0,0 +1,20 @@
+// Some lessons are about code that does not compile. Those snippets live in tests/compile_fail, one program per file, and this test runs rustc on each of them to check that it still fails, and fails for the reason the lesson gives.
+// Every snippet starts with a few metadata comments. The `// expected:` line says what rustc must report, e.g. `// expected: error[E0308]`. The wont_compile crate at the top of the repository checks the snippets of all lessons at once, and these tests call the same code for the snippets of this lesson.
+
+use std::path::Path;
+
+// CARGO_TARGET_TMPDIR is a scratch directory Cargo provides to integration tests, for whatever rustc writes.
+fn check(name: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/compile_fail").join(name);
+    wont_compile::assert_fails(&path, Path::new(env!("CARGO_TARGET_TMPDIR")));
+}
+
+#[test]
+fn let_is_not_an_expression() {
+    check("let_as_expression.rs");
+}
+
+#[test]
+fn a_semicolon_turns_the_return_value_into_a_statement() {
+    check("plus_one_semicolon.rs");
+}
@@ -0,0 +1,7 @@
+// lesson: functions
+// expected: expected expression, found `let` statement
+// explanation: Statements do not return values, therefore, you can't assign a let statement to another variable. The let y = 6 statement does not return a value, so there isn’t anything for x to bind to. In other languages, like C or Ruby, an assignment returns the value of the assignment, so you can write, e.g., x = y = 6.
+
+fn main() {
+    let x = (let y = 6);
+}
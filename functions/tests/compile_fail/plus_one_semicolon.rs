@@ -0,0 +1,13 @@
+// lesson: functions
+// expected: error[E0308]
+// explanation: This function does not compile, since the semicolon turns x + 1 into a statement. A statement does not evaluate to a value, so the body evaluates to (), the unit type, instead of the i32 the signature promises.
+
+fn plus_one(x: i32) -> i32 {
+    x + 1;
+}
+
+fn main() {
+    let x = plus_one(5);
+
+    println!("The value of x is: {x}");
+}
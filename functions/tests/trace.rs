@@ -0,0 +1,27 @@
+// Runs the functions program with and without FUNCTIONS_TRACE, to check that the variable switches tracing on while the program runs. CARGO_BIN_EXE_functions is the path of the binary Cargo built for the tests.
+
+use std::process::{Command, Output};
+
+fn run(trace: bool, arguments: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_functions"));
+    command.args(arguments).env_remove("FUNCTIONS_TRACE");
+    if trace {
+        command.env("FUNCTIONS_TRACE", "1");
+    }
+    command.output().expect("the functions binary could not be started")
+}
+
+#[test]
+fn traces_nested_calls_when_the_variable_is_set() {
+    let output = run(true, &["run", "call_five"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "call_five()\n  five()\n  → five() = 5\n→ call_five() = ()\n");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "The value of x is: 5\n");
+}
+
+#[test]
+fn prints_no_trace_without_the_variable() {
+    let output = run(false, &["run", "plus_one", "41"]);
+    assert!(output.stderr.is_empty());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "=> 42\n");
+}
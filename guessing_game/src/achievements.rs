@@ -0,0 +1,241 @@
+// Achievements are unlocked by looking at the events of each finished game. Most of them only need a single game, the streak needs to remember how the previous games went. Progress is kept per player in achievements.tsv: name, games played, current streak, best streak and the unlocked achievements separated by commas.
+
+use std::collections::HashMap;
+use std::io;
+
+use crate::record::{Event, Outcome};
+use crate::storage;
+
+const FILE: &str = "achievements.tsv";
+const STREAK_GOAL: u32 = 10;
+
+// What an achievement asks for. Game achievements are plain functions over the events of one game; fn(&[Event]) -> bool is a function pointer type.
+pub enum Goal {
+    Game(fn(&[Event]) -> bool),
+    WinStreak(u32),
+}
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub goal: Goal,
+}
+
+fn won(events: &[Event]) -> bool {
+    events.contains(&Event::Finished { outcome: Outcome::Won })
+}
+
+// filter_map keeps the values for which the closure returns Some, so this yields the optimal flag of every counted guess.
+fn guesses(events: &[Event]) -> impl Iterator<Item = bool> + '_ {
+    events.iter().filter_map(|event| match event {
+        Event::Guessed { optimal, .. } => Some(*optimal),
+        _ => None,
+    })
+}
+
+fn never_redundant(events: &[Event]) -> bool {
+    !events.iter().any(|event| matches!(event, Event::Redundant { .. }))
+}
+
+pub static ALL: [Achievement; 5] = [
+    Achievement {
+        id: "first-win",
+        name: "Winner",
+        description: "Win a game",
+        goal: Goal::Game(won),
+    },
+    Achievement {
+        id: "one-guess",
+        name: "Mind reader",
+        description: "Win with your very first guess",
+        goal: Goal::Game(|events| won(events) && guesses(events).count() == 1),
+    },
+    Achievement {
+        id: "binary-search",
+        name: "Binary search",
+        description: "Win a game of at least two guesses where every guess was in the middle of the feasible interval",
+        goal: Goal::Game(|events| won(events) && guesses(events).count() >= 2 && guesses(events).all(|optimal| optimal) && never_redundant(events)),
+    },
+    Achievement {
+        id: "clean",
+        name: "No wasted breath",
+        description: "Win without ever guessing outside the feasible interval",
+        goal: Goal::Game(|events| won(events) && never_redundant(events)),
+    },
+    Achievement {
+        id: "streak-10",
+        name: "On a roll",
+        description: "Win ten games in a row",
+        goal: Goal::WinStreak(STREAK_GOAL),
+    },
+];
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Progress {
+    pub games: u32,
+    pub streak: u32,
+    pub best_streak: u32,
+    pub unlocked: Vec<String>,
+}
+
+impl Progress {
+    pub fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        self.unlocked.iter().any(|id| id == achievement.id)
+    }
+
+    // Returns how far along the player is, and how far they need to get.
+    pub fn progress(&self, achievement: &Achievement) -> (u32, u32) {
+        let done = self.is_unlocked(achievement);
+        match achievement.goal {
+            Goal::Game(_) => (done as u32, 1),
+            Goal::WinStreak(goal) if done => (goal, goal),
+            Goal::WinStreak(goal) => (self.streak.min(goal), goal),
+        }
+    }
+
+    // Updates the progress with one more game and returns the achievements it unlocked.
+    pub fn record_game(&mut self, events: &[Event]) -> Vec<&'static Achievement> {
+        self.games += 1;
+        self.streak = if won(events) { self.streak + 1 } else { 0 };
+        self.best_streak = self.best_streak.max(self.streak);
+
+        let mut unlocked = Vec::new();
+        for achievement in &ALL {
+            let reached = match achievement.goal {
+                Goal::Game(check) => check(events),
+                Goal::WinStreak(goal) => self.streak >= goal,
+            };
+            if reached && !self.is_unlocked(achievement) {
+                self.unlocked.push(achievement.id.to_string());
+                unlocked.push(achievement);
+            }
+        }
+        unlocked
+    }
+
+    fn parse(line: &str) -> Option<(String, Progress)> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, games, streak, best_streak, unlocked] = fields.as_slice() else {
+            return None;
+        };
+        let progress = Progress {
+            games: games.parse().ok()?,
+            streak: streak.parse().ok()?,
+            best_streak: best_streak.parse().ok()?,
+            unlocked: unlocked.split(',').filter(|id| !id.is_empty()).map(String::from).collect(),
+        };
+        Some((name.to_string(), progress))
+    }
+}
+
+fn load() -> io::Result<HashMap<String, Progress>> {
+    let mut players = HashMap::new();
+    for line in storage::read(FILE)?.lines() {
+        match Progress::parse(line) {
+            Some((name, progress)) => {
+                players.insert(name, progress);
+            }
+            None => eprintln!("Skipping a broken line in {FILE}: {line}"),
+        }
+    }
+    Ok(players)
+}
+
+fn save(players: &HashMap<String, Progress>) -> io::Result<()> {
+    let mut names: Vec<&String> = players.keys().collect();
+    names.sort();
+
+    let mut contents = String::new();
+    for name in names {
+        let p = &players[name];
+        contents += &format!("{name}\t{}\t{}\t{}\t{}\n", p.games, p.streak, p.best_streak, p.unlocked.join(","));
+    }
+    storage::write(FILE, &contents)
+}
+
+// Called at the end of every game the player played.
+pub fn record(player: &str, events: &[Event]) {
+    let result = load().and_then(|mut players| {
+        let unlocked = players.entry(player.to_string()).or_default().record_game(events);
+        for achievement in unlocked {
+            println!("Achievement unlocked: {} ({})", achievement.name, achievement.description);
+        }
+        save(&players)
+    });
+    if let Err(error) = result {
+        eprintln!("Could not update the achievements: {error}");
+    }
+}
+
+pub fn show(player: &str) {
+    let players = match load() {
+        Ok(players) => players,
+        Err(error) => {
+            eprintln!("Could not read the achievements: {error}");
+            return;
+        }
+    };
+    let empty = Progress::default();
+    let progress = players.get(player).unwrap_or(&empty);
+
+    println!("Achievements of {player} ({} games played, best streak {}):", progress.games, progress.best_streak);
+    for achievement in &ALL {
+        let (done, goal) = progress.progress(achievement);
+        let mark = if progress.is_unlocked(achievement) { "[x]" } else { "[ ]" };
+        println!("  {mark} {:<18} {done}/{goal}  {}", achievement.name, achievement.description);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    fn game(guesses: &[(Ordering, bool)], outcome: Outcome) -> Vec<Event> {
+        let mut events = vec![Event::Started { range: "1..=100".to_string() }];
+        for &(ordering, optimal) in guesses {
+            events.push(Event::Guessed { guess: String::new(), ordering, optimal });
+        }
+        events.push(Event::Finished { outcome });
+        events
+    }
+
+    fn ids(unlocked: Vec<&Achievement>) -> Vec<&str> {
+        unlocked.iter().map(|a| a.id).collect()
+    }
+
+    #[test]
+    fn unlocks_from_the_event_stream() {
+        let mut progress = Progress::default();
+
+        let lucky = game(&[(Ordering::Equal, false)], Outcome::Won);
+        assert_eq!(ids(progress.record_game(&lucky)), ["first-win", "one-guess", "clean"]);
+
+        let search = game(&[(Ordering::Less, true), (Ordering::Equal, true)], Outcome::Won);
+        assert_eq!(ids(progress.record_game(&search)), ["binary-search"]);
+
+        let mut sloppy = game(&[(Ordering::Less, true), (Ordering::Equal, true)], Outcome::Won);
+        sloppy.insert(2, Event::Redundant { guess: String::new(), reason: crate::game::Redundant::Repeated });
+        assert_eq!(ids(Progress::default().record_game(&sloppy)), ["first-win"]);
+    }
+
+    #[test]
+    fn tracks_the_win_streak() {
+        let mut progress = Progress::default();
+        let win = game(&[(Ordering::Equal, true)], Outcome::Won);
+        for _ in 0..9 {
+            progress.record_game(&win);
+        }
+        assert_eq!(progress.progress(&ALL[4]), (9, 10));
+
+        progress.record_game(&game(&[], Outcome::GaveUp));
+        assert_eq!(progress.progress(&ALL[4]), (0, 10));
+
+        for _ in 0..10 {
+            progress.record_game(&win);
+        }
+        assert!(progress.is_unlocked(&ALL[4]));
+        assert_eq!(progress.best_streak, 10);
+    }
+}
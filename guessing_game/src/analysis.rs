@@ -0,0 +1,198 @@
+// Information theory measures information in bits. One bit is what you learn from the answer to a fair yes-or-no question, so halving the feasible interval gains exactly one bit, and finding one number out of 100 takes log2(100) ≈ 6.64 bits in total.
+// Everything in here is a pure function of the guesses and answers, so it can be computed after the fact or tested without playing.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::game::Guessable;
+use crate::interval::Interval;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<T> {
+    pub guess: T,
+    pub before: f64,
+    pub after: f64,
+    // What the answer actually taught, log2(before / after).
+    pub gained: f64,
+    // What the guess was worth on average before the answer came in.
+    pub expected: f64,
+    // The midpoint of the interval, which is worth the most on average.
+    pub best: T,
+    pub best_expected: f64,
+}
+
+// The smallest interval that still counts as found: one value, or for floats the window around the secret where guesses win.
+fn resolution<T: Guessable>(tolerance: f64) -> f64 {
+    if T::CONTINUOUS { (2.0 * tolerance).max(f64::EPSILON) } else { 1.0 }
+}
+
+// The entropy of the three possible answers. Each answer has a probability proportional to the part of the interval it leaves, and an answer with probability p is worth log2(1 / p) bits.
+pub fn entropy(parts: &[f64]) -> f64 {
+    let total: f64 = parts.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    parts
+        .iter()
+        .filter(|&&part| part > 0.0)
+        .map(|&part| {
+            let p = part / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+// How much a guess is worth on average: the interval splits into values below it, the guess itself, and values above it. A guess outside the interval is worth nothing, since the answer is already known.
+pub fn expected_bits<T: Guessable>(interval: &Interval<T>, tolerance: f64, guess: T) -> f64 {
+    if !interval.contains(guess) {
+        return 0.0;
+    }
+    let hit = resolution::<T>(tolerance);
+    let below = guess.position() - interval.low().position();
+    let above = interval.high().position() - guess.position();
+    entropy(&[below.max(0.0), hit, above.max(0.0)])
+}
+
+pub fn midpoint<T: Guessable>(interval: &Interval<T>) -> T {
+    T::from_position((interval.low().position() + interval.high().position()) / 2.0)
+}
+
+// Analyzes one guess, given the interval that was feasible right before it.
+pub fn step<T: Guessable>(interval: &Interval<T>, tolerance: f64, guess: T, ordering: Ordering) -> Step<T> {
+    let before = interval.size();
+    let after = if ordering == Ordering::Equal {
+        resolution::<T>(tolerance)
+    } else {
+        let mut narrowed = *interval;
+        narrowed.narrow(guess, ordering);
+        narrowed.size()
+    };
+
+    let best = midpoint(interval);
+    Step {
+        guess,
+        before,
+        after,
+        gained: if after > 0.0 { (before / after).log2().max(0.0) } else { 0.0 },
+        expected: expected_bits(interval, tolerance, guess),
+        best,
+        best_expected: expected_bits(interval, tolerance, best),
+    }
+}
+
+// Replays a whole game from the start.
+pub fn analyze<T: Guessable>(range: &RangeInclusive<T>, tolerance: f64, history: &[(T, Ordering)]) -> Vec<Step<T>> {
+    let mut interval = Interval::new(range);
+    let mut steps = Vec::new();
+    for &(guess, ordering) in history {
+        steps.push(step(&interval, tolerance, guess, ordering));
+        interval.narrow(guess, ordering);
+    }
+    steps
+}
+
+impl<T: Guessable> fmt::Display for Step<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Analysis: gained {:.2} bits (size {} -> {}). The guess was worth {:.2} bits, the midpoint {} would have been worth {:.2}.",
+            self.gained, self.before, self.after, self.expected, self.best, self.best_expected
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Report {
+    pub guesses: usize,
+    pub needed: f64,
+    pub gained: f64,
+    pub expected: f64,
+    pub best_expected: f64,
+    // Only for discrete types: binary search never needs more than this many guesses.
+    pub binary_search: Option<u32>,
+}
+
+impl Report {
+    pub fn new<T: Guessable>(range: &RangeInclusive<T>, tolerance: f64, steps: &[Step<T>]) -> Report {
+        let size = Interval::new(range).size();
+        Report {
+            guesses: steps.len(),
+            needed: (size / resolution::<T>(tolerance)).log2().max(0.0),
+            gained: steps.iter().map(|s| s.gained).sum(),
+            expected: steps.iter().map(|s| s.expected).sum(),
+            best_expected: steps.iter().map(|s| s.best_expected).sum(),
+            binary_search: (!T::CONTINUOUS).then(|| (size + 1.0).log2().ceil() as u32),
+        }
+    }
+
+    // The share of the available information the guesses went for, 1.0 when every guess was a midpoint.
+    pub fn efficiency(&self) -> f64 {
+        if self.best_expected > 0.0 { self.expected / self.best_expected } else { 1.0 }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Analysis of the game:")?;
+        writeln!(f, "  Finding the secret takes {:.2} bits. Your {} guesses gained {:.2}.", self.needed, self.guesses, self.gained)?;
+        write!(
+            f,
+            "  On average your guesses were worth {:.2} bits, midpoint guesses {:.2}. Efficiency: {:.0}%",
+            self.expected,
+            self.best_expected,
+            self.efficiency() * 100.0
+        )?;
+        if let Some(worst_case) = self.binary_search {
+            write!(f, "\n  Binary search needs at most {worst_case} guesses for this range.")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} is not close to {expected}");
+    }
+
+    #[test]
+    fn entropy_of_splits() {
+        assert_close(entropy(&[1.0, 1.0]), 1.0);
+        assert_close(entropy(&[1.0, 1.0, 1.0, 1.0]), 2.0);
+        assert_close(entropy(&[0.0, 1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn halving_gains_one_bit() {
+        let interval = Interval::new(&(1u32..=8));
+        let step = step(&interval, 0.0, 4, Ordering::Less);
+        assert_eq!((step.before, step.after), (8.0, 4.0));
+        assert_close(step.gained, 1.0);
+        assert_eq!(step.best, 5); // 4.5 rounds away from zero
+        assert_close(step.expected, entropy(&[3.0, 1.0, 4.0]));
+        assert_close(step.best_expected, entropy(&[4.0, 1.0, 3.0]));
+    }
+
+    #[test]
+    fn guesses_outside_the_interval_are_worth_nothing() {
+        let history = [(50, Ordering::Greater), (70, Ordering::Greater)];
+        let steps = analyze(&(1u32..=100), 0.0, &history);
+        assert_eq!(steps[1].expected, 0.0);
+        assert_eq!(steps[1].gained, 0.0);
+    }
+
+    #[test]
+    fn report_sums_up_a_game() {
+        let history = [(4, Ordering::Less), (6, Ordering::Greater), (5, Ordering::Equal)];
+        let steps = analyze(&(1u32..=8), 0.0, &history);
+        let report = Report::new(&(1u32..=8), 0.0, &steps);
+
+        assert_close(report.needed, 3.0);
+        assert_close(report.gained, 3.0);
+        assert_eq!(report.binary_search, Some(4));
+        assert!(report.efficiency() > 0.9 && report.efficiency() <= 1.0);
+    }
+}
@@ -0,0 +1,120 @@
+// A computer player. It keeps track of the values the secret can still be, and guesses right in the middle of them. This is binary search, and it needs at most 7 guesses for the numbers from 1 to 100.
+// To make it beatable, the bot can be told to make mistakes: some share of its guesses are then picked at random from the feasible interval instead.
+
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::analysis;
+use crate::game::{Game, Guessable};
+use crate::interval::Interval;
+use crate::record::{GameRecord, GuessRecord, Outcome};
+use crate::timer::{SystemClock, Timer};
+
+pub const NAME: &str = "bot";
+
+pub struct Bot<T> {
+    feasible: Interval<T>,
+    // Between 0.0 (always the midpoint) and 1.0 (always random).
+    mistake_rate: f64,
+}
+
+impl<T: Guessable> Bot<T> {
+    pub fn new(range: &RangeInclusive<T>, mistake_rate: f64) -> Bot<T> {
+        Bot { feasible: Interval::new(range), mistake_rate: mistake_rate.clamp(0.0, 1.0) }
+    }
+
+    pub fn next_guess<R: Rng>(&self, rng: &mut R) -> T {
+        let (low, high) = (self.feasible.low(), self.feasible.high());
+        if rng.gen_bool(self.mistake_rate) && low < high {
+            rng.gen_range(low..=high)
+        } else {
+            analysis::midpoint(&self.feasible)
+        }
+    }
+
+    // The bot learns from every answer, its own or, when feedback is shared, the other player's.
+    pub fn learn(&mut self, guess: T, ordering: Ordering) {
+        self.feasible.narrow(guess, ordering);
+    }
+}
+
+// Plays a whole game on its own, for the bot's turns in a match.
+pub fn play<T: Guessable>(mut game: Game<T>, mistake_rate: f64) -> GameRecord {
+    let mut bot = Bot::new(game.range(), mistake_rate);
+    let mut timer = Timer::start(SystemClock, None);
+    let mut guesses = Vec::new();
+
+    let outcome = loop {
+        let guess = bot.next_guess(&mut rand::thread_rng());
+        let ordering = match game.guess(guess) {
+            Some(ordering) => ordering,
+            None => break Outcome::GaveUp,
+        };
+        guesses.push(GuessRecord { guess: guess.to_string(), ordering, time: timer.lap() });
+        bot.learn(guess, ordering);
+
+        match ordering {
+            Ordering::Less => println!("The bot guessed {guess}: Too small!"),
+            Ordering::Greater => println!("The bot guessed {guess}: Too big!"),
+            Ordering::Equal => {
+                println!("The bot guessed {guess} and wins after {} guesses!", game.attempts());
+                break Outcome::Won;
+            }
+        }
+    };
+
+    GameRecord { outcome, guesses, total: timer.elapsed(), time_limit: None, events: Vec::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // The guesses the bot makes until it finds the secret, giving up after 100.
+    fn guesses(secret: u32, mistake_rate: f64, rng: &mut StdRng) -> Vec<(u32, u32)> {
+        let mut bot = Bot::new(&(1..=100), mistake_rate);
+        let mut guesses = Vec::new();
+        while guesses.len() < 100 {
+            let midpoint = analysis::midpoint(&bot.feasible);
+            let guess = bot.next_guess(rng);
+            assert!(bot.feasible.contains(guess), "{guess} is outside {}", bot.feasible);
+            guesses.push((guess, midpoint));
+            let ordering = guess.cmp(&secret);
+            bot.learn(guess, ordering);
+            assert!(ordering == Ordering::Equal || bot.feasible.contains(secret));
+            if ordering == Ordering::Equal {
+                break;
+            }
+        }
+        guesses
+    }
+
+    #[test]
+    fn without_mistakes_it_always_takes_the_midpoint() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for secret in 1..=100 {
+            let guesses = guesses(secret, 0.0, &mut rng);
+            assert!(guesses.iter().all(|(guess, midpoint)| guess == midpoint));
+            assert!(guesses.len() <= 7, "{secret} took {} guesses", guesses.len());
+        }
+    }
+
+    #[test]
+    fn with_only_mistakes_it_never_searches() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut midpoints = 0;
+        let mut total = 0;
+        for secret in 1..=100 {
+            let guesses = guesses(secret, 1.0, &mut rng);
+            assert_eq!(guesses.last().map(|(guess, _)| *guess), Some(secret));
+            midpoints += guesses.iter().filter(|(guess, midpoint)| guess == midpoint).count();
+            total += guesses.len();
+        }
+        // Random guesses still land on the midpoint now and then, but only by chance.
+        assert!(midpoints * 4 < total, "{midpoints} of {total} guesses were the midpoint");
+    }
+}
@@ -0,0 +1,318 @@
+// Bulls and cows is the digit ancestor of Mastermind. The secret is a four digit code where every digit is different, e.g. 4271. Instead of "Too small!" or "Too big!", every guess is answered with a number of bulls (right digit in the right place) and cows (right digit in the wrong place).
+
+use std::fmt;
+use std::io;
+
+use rand::seq::SliceRandom;
+
+pub const CODE_LENGTH: usize = 4;
+
+// Knuth showed that a minimax strategy solves the original Mastermind in at most five moves. For four distinct digits the same idea never needs more than seven.
+pub const MAX_MOVES: usize = 7;
+
+// A tuple struct wraps a single value in a new type. Deriving Copy is fine here since the array is only four bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Code([u8; CODE_LENGTH]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub bulls: u8,
+    pub cows: u8,
+}
+
+// Enums can carry data in each variant, which makes them a natural fit for describing why some input was rejected.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CodeError {
+    WrongLength(usize),
+    NotADigit(char),
+    RepeatedDigit(char),
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodeError::WrongLength(len) => write!(f, "a code has {CODE_LENGTH} digits, but you entered {len}"),
+            CodeError::NotADigit(c) => write!(f, "'{c}' is not a digit"),
+            CodeError::RepeatedDigit(c) => write!(f, "the digit {c} is used more than once"),
+        }
+    }
+}
+
+impl Code {
+    pub fn parse(input: &str) -> Result<Code, CodeError> {
+        let chars: Vec<char> = input.trim().chars().collect();
+        if chars.len() != CODE_LENGTH {
+            return Err(CodeError::WrongLength(chars.len()));
+        }
+
+        let mut digits = [0; CODE_LENGTH];
+        for (i, &c) in chars.iter().enumerate() {
+            let digit = c.to_digit(10).ok_or(CodeError::NotADigit(c))? as u8; // The ? operator returns the error early, just like a match with an Err arm that returns.
+            if digits[..i].contains(&digit) {
+                return Err(CodeError::RepeatedDigit(c));
+            }
+            digits[i] = digit;
+        }
+
+        Ok(Code(digits))
+    }
+
+    // Every code with distinct digits: 10 * 9 * 8 * 7 = 5040 of them, in ascending order.
+    pub fn all() -> Vec<Code> {
+        let mut codes = Vec::new();
+        for a in 0..10 {
+            for b in 0..10 {
+                for c in 0..10 {
+                    for d in 0..10 {
+                        let digits = [a, b, c, d];
+                        let distinct = (0..CODE_LENGTH).all(|i| !digits[..i].contains(&digits[i]));
+                        if distinct {
+                            codes.push(Code(digits));
+                        }
+                    }
+                }
+            }
+        }
+        codes
+    }
+
+    pub fn random() -> Code {
+        let mut digits: Vec<u8> = (0..10).collect();
+        digits.shuffle(&mut rand::thread_rng());
+        Code([digits[0], digits[1], digits[2], digits[3]])
+    }
+
+    // The score is symmetric: scoring a guess against the secret gives the same answer as scoring the secret against the guess.
+    pub fn score(&self, other: &Code) -> Score {
+        let mut bulls = 0;
+        let mut common = 0;
+        for i in 0..CODE_LENGTH {
+            if self.0[i] == other.0[i] {
+                bulls += 1;
+            }
+            if other.0.contains(&self.0[i]) {
+                common += 1;
+            }
+        }
+        Score { bulls, cows: common - bulls }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in self.0 {
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Score {
+    pub const WIN: Score = Score { bulls: CODE_LENGTH as u8, cows: 0 };
+
+    // Bulls and cows each range over 0..=4, so a score fits into one of 25 buckets.
+    fn index(&self) -> usize {
+        self.bulls as usize * (CODE_LENGTH + 1) + self.cows as usize
+    }
+
+    pub fn parse(input: &str) -> Option<Score> {
+        let mut numbers = input.split_whitespace().map(|n| n.parse::<u8>());
+        match (numbers.next(), numbers.next(), numbers.next()) {
+            (Some(Ok(bulls)), Some(Ok(cows)), None) if bulls as usize + cows as usize <= CODE_LENGTH => {
+                Some(Score { bulls, cows })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bulls, {} cows", self.bulls, self.cows)
+    }
+}
+
+// The solver keeps every code that is still consistent with the answers so far.
+pub struct Solver {
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+}
+
+impl Solver {
+    pub fn new() -> Solver {
+        let all = Code::all();
+        Solver { candidates: all.clone(), all }
+    }
+
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    // Knuth-style minimax: pick the guess whose worst possible answer leaves the fewest candidates. Ties go to guesses that could still be the secret, then to the smallest code.
+    pub fn next_guess(&self) -> Option<Code> {
+        match self.candidates.len() {
+            0 => return None,
+            1 | 2 => return Some(self.candidates[0]),
+            // Before the first answer every guess is equivalent up to renaming digits, so there is no point in searching.
+            n if n == self.all.len() => return Some(self.all[0]),
+            _ => {}
+        }
+
+        let mut best: Option<(usize, bool, Code)> = None;
+        for &guess in &self.all {
+            let mut buckets = [0; (CODE_LENGTH + 1) * (CODE_LENGTH + 1)];
+            for candidate in &self.candidates {
+                buckets[guess.score(candidate).index()] += 1;
+            }
+            let worst = *buckets.iter().max().unwrap();
+            let is_candidate = buckets[Score::WIN.index()] > 0;
+
+            let better = match best {
+                None => true,
+                Some((best_worst, best_is_candidate, _)) => {
+                    worst < best_worst || (worst == best_worst && is_candidate && !best_is_candidate)
+                }
+            };
+            if better {
+                best = Some((worst, is_candidate, guess));
+            }
+        }
+
+        best.map(|(_, _, guess)| guess)
+    }
+
+    // Throw away every candidate that would have produced a different answer to this guess.
+    pub fn record(&mut self, guess: Code, score: Score) {
+        self.candidates.retain(|candidate| guess.score(candidate) == score);
+    }
+}
+
+// The player tries to find the computer's code.
+pub fn play() {
+    println!("Bulls and cows! Guess the {CODE_LENGTH} digit code, every digit is different.");
+
+    let secret = Code::random();
+    let mut attempts = 0;
+
+    loop {
+        println!("Please input your guess.");
+
+        let mut guess = String::new();
+        io::stdin()
+            .read_line(&mut guess)
+            .expect("Failed to read line");
+
+        if guess.is_empty() {
+            println!("The code was {secret}.");
+            return;
+        }
+
+        let guess = match Code::parse(&guess) {
+            Ok(code) => code,
+            Err(error) => {
+                println!("Invalid guess: {error}.");
+                continue;
+            }
+        };
+        attempts += 1;
+
+        let score = guess.score(&secret);
+        if score == Score::WIN {
+            println!("You win after {attempts} guesses!");
+            break;
+        }
+        println!("{guess}: {score}");
+    }
+}
+
+// The computer tries to find a code the player has in mind.
+pub fn solve() {
+    println!("Think of a {CODE_LENGTH} digit code with different digits. I will find it in at most {MAX_MOVES} guesses.");
+    println!("Answer each guess with the number of bulls and cows, e.g. `1 2`.");
+
+    let mut solver = Solver::new();
+    let mut moves = 0;
+
+    while let Some(guess) = solver.next_guess() {
+        moves += 1;
+        println!("Guess {moves}: {guess} ({} codes left)", solver.candidates().len());
+
+        let score = loop {
+            let mut answer = String::new();
+            io::stdin()
+                .read_line(&mut answer)
+                .expect("Failed to read line");
+
+            if answer.is_empty() {
+                return;
+            }
+
+            match Score::parse(&answer) {
+                Some(score) => break score, // break can hand a value out of a loop, which makes the loop an expression.
+                None => println!("Please answer with two numbers, bulls then cows."),
+            }
+        };
+
+        if score == Score::WIN {
+            println!("Found it in {moves} guesses!");
+            return;
+        }
+        solver.record(guess, score);
+    }
+
+    println!("No code matches those answers, one of them must have been wrong.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_bulls_and_cows() {
+        let secret = Code::parse("4271").unwrap();
+        assert_eq!(Code::parse("1234").unwrap().score(&secret), Score { bulls: 1, cows: 2 });
+        assert_eq!(Code::parse("4170").unwrap().score(&secret), Score { bulls: 2, cows: 1 });
+        assert_eq!(secret.score(&secret), Score::WIN);
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        assert_eq!(Code::parse("123"), Err(CodeError::WrongLength(3)));
+        assert_eq!(Code::parse("12345"), Err(CodeError::WrongLength(5)));
+        assert_eq!(Code::parse("12a4"), Err(CodeError::NotADigit('a')));
+        assert_eq!(Code::parse("1231"), Err(CodeError::RepeatedDigit('1')));
+        assert!(Code::parse(" 0987\n").is_ok());
+    }
+
+    #[test]
+    fn there_are_5040_codes() {
+        assert_eq!(Code::all().len(), 5040);
+    }
+
+    // Playing 5040 separate games would repeat the same searches over and over. The solver only depends on the answers it has seen, so we walk the tree of answers instead and reach every secret exactly once.
+    fn solve_all(solver: Solver, depth: usize, solved: &mut usize) {
+        let guess = solver.next_guess().expect("no guess for a non-empty candidate set");
+        assert!(depth <= MAX_MOVES, "needed more than {MAX_MOVES} moves");
+
+        let mut answers: Vec<Score> = solver.candidates().iter().map(|c| guess.score(c)).collect();
+        answers.sort_by_key(|s| s.index());
+        answers.dedup();
+
+        for score in answers {
+            if score == Score::WIN {
+                *solved += 1;
+                continue;
+            }
+            let mut next = Solver { all: solver.all.clone(), candidates: solver.candidates.clone() };
+            next.record(guess, score);
+            solve_all(next, depth + 1, solved);
+        }
+    }
+
+    #[test]
+    fn solver_wins_every_game_within_max_moves() {
+        let mut solved = 0;
+        solve_all(Solver::new(), 1, &mut solved);
+        assert_eq!(solved, Code::all().len());
+    }
+}
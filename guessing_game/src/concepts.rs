@@ -0,0 +1,156 @@
+// The Rust concepts the game uses, as data instead of comments. With --explain the game prints a short note from this table whenever it reaches one of them, so learners see each concept the moment it happens.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Concept {
+    Rng,
+    Loop,
+    MutableString,
+    ReadLine,
+    Expect,
+    Trim,
+    ParseOk,
+    ParseErr,
+    Shadowing,
+    Cmp,
+    MatchArm,
+    Break,
+}
+
+pub struct Entry {
+    pub concept: Concept,
+    pub title: &'static str,
+    // {name} placeholders are filled in with the values of the current step.
+    pub template: &'static str,
+}
+
+pub static TABLE: [Entry; 12] = [
+    Entry {
+        concept: Concept::Rng,
+        title: "traits in scope",
+        template: "`rand::thread_rng().{sampler}(...)` picked the secret from {range}, with the {distribution} distribution. {sampler} is a method of the Rng trait, which is why `use rand::Rng` is needed.",
+    },
+    Entry {
+        concept: Concept::Loop,
+        title: "loop",
+        template: "`loop` starts iteration {iteration}. It only ends with `break` or `return`.",
+    },
+    Entry {
+        concept: Concept::MutableString,
+        title: "mutable variables",
+        template: "`let mut guess = String::new()` creates an empty, growable String. Without `mut`, read_line could not change it.",
+    },
+    Entry {
+        concept: Concept::ReadLine,
+        title: "references",
+        template: "`read_line(&mut guess)` appended {bytes} bytes to the String it borrowed through a mutable reference.",
+    },
+    Entry {
+        concept: Concept::Expect,
+        title: "Result and expect",
+        template: "read_line returned `Ok({bytes})`, so `expect` handed over the value. An `Err` would have crashed the program with \"Failed to read line\".",
+    },
+    Entry {
+        concept: Concept::Trim,
+        title: "string slices",
+        template: "`trim()` returned a &str without the surrounding whitespace: {input:?}. No new String is allocated.",
+    },
+    Entry {
+        concept: Concept::ParseOk,
+        title: "match on Result",
+        template: "parsing returned `Ok({value})`, and the `Ok(num) => num` arm took the number out.",
+    },
+    Entry {
+        concept: Concept::ParseErr,
+        title: "early return",
+        template: "parsing returned `Err`, so the `Err` arm ran `return None`. The turn ends without an outcome, and the loop asks for the next guess.",
+    },
+    Entry {
+        concept: Concept::Shadowing,
+        title: "shadowing",
+        template: "shadowing `guess` from String to {type}. The old String is no longer reachable by that name.",
+    },
+    Entry {
+        concept: Concept::Cmp,
+        title: "Ordering",
+        template: "`{method}` returned Ordering::{ordering}.",
+    },
+    Entry {
+        concept: Concept::MatchArm,
+        title: "match arms",
+        template: "the `Ordering::{ordering}` arm of the match ran. A match must cover every variant, so there is an arm for each.",
+    },
+    Entry {
+        concept: Concept::Break,
+        title: "break",
+        template: "`break` ends the loop and hands {outcome} out of it as the value of the loop expression.",
+    },
+];
+
+pub fn entry(concept: Concept) -> &'static Entry {
+    TABLE.iter().find(|entry| entry.concept == concept).expect("every concept has an entry in the table")
+}
+
+// Fills in the placeholders of the concept's template.
+pub fn describe(concept: Concept, values: &[(&str, &str)]) -> String {
+    let entry = entry(concept);
+    let mut text = entry.template.to_string();
+    for (name, value) in values {
+        text = text.replace(&format!("{{{name}}}"), value);
+        text = text.replace(&format!("{{{name}:?}}"), &format!("{value:?}"));
+    }
+    format!("  [{}] {text}", entry.title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Values for every placeholder a template uses. The match has no wildcard arm, so a new concept does not compile until it is added here.
+    fn values(concept: Concept) -> &'static [(&'static str, &'static str)] {
+        match concept {
+            Concept::Rng => &[("sampler", "gen_range"), ("range", "1..=100"), ("distribution", "uniform")],
+            Concept::Loop => &[("iteration", "1")],
+            Concept::MutableString | Concept::ParseErr => &[],
+            Concept::ReadLine | Concept::Expect => &[("bytes", "3")],
+            Concept::Trim => &[("input", "42")],
+            Concept::ParseOk => &[("value", "42")],
+            Concept::Shadowing => &[("type", "u32")],
+            Concept::Cmp => &[("method", "cmp"), ("ordering", "Less")],
+            Concept::MatchArm => &[("ordering", "Less")],
+            Concept::Break => &[("outcome", "Outcome::Won")],
+        }
+    }
+
+    #[test]
+    fn every_concept_has_a_complete_entry() {
+        let all = [
+            Concept::Rng,
+            Concept::Loop,
+            Concept::MutableString,
+            Concept::ReadLine,
+            Concept::Expect,
+            Concept::Trim,
+            Concept::ParseOk,
+            Concept::ParseErr,
+            Concept::Shadowing,
+            Concept::Cmp,
+            Concept::MatchArm,
+            Concept::Break,
+        ];
+        assert_eq!(all.len(), TABLE.len());
+        for concept in all {
+            assert_eq!(entry(concept).concept, concept);
+            let text = describe(concept, values(concept));
+            assert!(!text.contains('{') && !text.contains('}'), "{concept:?} has a placeholder left: {text}");
+        }
+    }
+
+    #[test]
+    fn names_the_sampler() {
+        let text = describe(Concept::Rng, &[("sampler", "gen"), ("range", "0..=100"), ("distribution", "gaussian")]);
+        assert_eq!(
+            text,
+            "  [traits in scope] `rand::thread_rng().gen(...)` picked the secret from 0..=100, with the gaussian distribution. gen is a method of the Rng trait, which is why `use rand::Rng` is needed."
+        );
+    }
+}
@@ -0,0 +1,307 @@
+// gen_range draws every value with the same probability. These distributions make some secrets more likely than others, using nothing but the uniform random numbers that rand::Rng provides.
+
+use std::f64::consts::PI;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::game::Guessable;
+use crate::options::Options;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Uniform,
+    // Most likely in the middle of the range, falling off linearly towards both ends.
+    Triangular,
+    // Half of the time the secret is a multiple of ten.
+    RoundNumbers,
+    // A bell curve around the middle with a standard deviation of a sixth of the range. Draws outside the range are clamped to its ends.
+    Gaussian,
+}
+
+pub const ALL: [Distribution; 4] = [Distribution::Uniform, Distribution::Triangular, Distribution::RoundNumbers, Distribution::Gaussian];
+
+const ROUND: i64 = 10;
+const ROUND_SHARE: f64 = 0.5;
+
+// The cumulative distribution function of the standard normal distribution, Φ(x) = (1 + erf(x / √2)) / 2. erf is approximated with formula 7.1.26 from Abramowitz and Stegun, which is accurate to about 1e-7.
+pub fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / 2f64.sqrt();
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 { (1.0 + erf) / 2.0 } else { (1.0 - erf) / 2.0 }
+}
+
+// The triangular distribution over [a, b] with its peak at c, as a cumulative distribution function and its inverse.
+fn triangular_cdf(x: f64, a: f64, b: f64, c: f64) -> f64 {
+    if x <= a {
+        0.0
+    } else if x <= c {
+        (x - a).powi(2) / ((b - a) * (c - a))
+    } else if x < b {
+        1.0 - (b - x).powi(2) / ((b - a) * (b - c))
+    } else {
+        1.0
+    }
+}
+
+fn triangular_inverse(u: f64, a: f64, b: f64, c: f64) -> f64 {
+    if u < (c - a) / (b - a) {
+        a + (u * (b - a) * (c - a)).sqrt()
+    } else {
+        b - ((1.0 - u) * (b - a) * (b - c)).sqrt()
+    }
+}
+
+// The multiples of ten in [low, high], as the first one and how many there are. The first one only means something when there are any.
+// The arithmetic is done in i128: near the ends of i64, rounding low up to a multiple of ten or taking high - first would overflow.
+fn round_numbers(low: i64, high: i64) -> (i64, i64) {
+    let (low, high, round) = (i128::from(low), i128::from(high), i128::from(ROUND));
+    let first = low.div_euclid(round) * round + if low.rem_euclid(round) == 0 { 0 } else { round };
+    let count = if first > high { 0 } else { (high - first) / round + 1 };
+    (first.min(high) as i64, count as i64)
+}
+
+impl Distribution {
+    pub fn parse(name: &str) -> Option<Distribution> {
+        ALL.into_iter().find(|d| d.to_string() == name)
+    }
+
+    // The Rng method sample starts with, for the --explain note on picking the secret.
+    pub fn sampler(&self) -> &'static str {
+        match self {
+            Distribution::Uniform => "gen_range",
+            Distribution::Triangular | Distribution::Gaussian => "gen",
+            Distribution::RoundNumbers => "gen_bool",
+        }
+    }
+
+    // Continuous types sample a point in [low, high]. Discrete types sample in [low - 0.5, high + 0.5] and round, so every value owns a slice of width one.
+    pub fn sample<T: Guessable, R: Rng>(&self, range: &RangeInclusive<T>, rng: &mut R) -> T {
+        let (low, high) = (range.start().position(), range.end().position());
+        let (a, b) = if T::CONTINUOUS { (low, high) } else { (low - 0.5, high + 0.5) };
+        let middle = (low + high) / 2.0;
+
+        let position = match self {
+            Distribution::Uniform => return rng.gen_range(range.clone()),
+            Distribution::Triangular => triangular_inverse(rng.r#gen::<f64>(), a, b, middle),
+            Distribution::RoundNumbers => {
+                let (first, count) = round_numbers(low.ceil() as i64, high.floor() as i64);
+                if count > 0 && rng.gen_bool(ROUND_SHARE) {
+                    // The sum always fits in an i64, but ten times a large index on its own may not.
+                    (i128::from(first) + i128::from(ROUND) * i128::from(rng.gen_range(0..count))) as f64
+                } else {
+                    return rng.gen_range(range.clone());
+                }
+            }
+            Distribution::Gaussian => {
+                // The Box-Muller transform turns two uniform numbers into one normally distributed number. 1 - gen() lies in (0, 1], so the logarithm is always defined.
+                let u1: f64 = 1.0 - rng.r#gen::<f64>();
+                let u2: f64 = rng.r#gen();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+                middle + z * (high - low) / 6.0
+            }
+        };
+
+        T::from_position(position.clamp(low, high))
+    }
+
+    // The probability of drawing each value from low to high, for a discrete range. These are the expected frequencies a chi-square test compares the samples with.
+    pub fn probabilities(&self, low: i64, high: i64) -> Vec<f64> {
+        let n = (high - low + 1) as f64;
+        let (a, b, middle) = (low as f64 - 0.5, high as f64 + 0.5, (low + high) as f64 / 2.0);
+        let sigma = (high - low) as f64 / 6.0;
+        let (_, round_count) = round_numbers(low, high);
+
+        (low..=high)
+            .map(|value| {
+                let (below, above) = (value as f64 - 0.5, value as f64 + 0.5);
+                match self {
+                    Distribution::Uniform => 1.0 / n,
+                    Distribution::Triangular => triangular_cdf(above, a, b, middle) - triangular_cdf(below, a, b, middle),
+                    Distribution::RoundNumbers if round_count == 0 => 1.0 / n,
+                    Distribution::RoundNumbers => {
+                        let round = if value.rem_euclid(ROUND) == 0 { ROUND_SHARE / round_count as f64 } else { 0.0 };
+                        (1.0 - ROUND_SHARE) / n + round
+                    }
+                    Distribution::Gaussian if sigma == 0.0 => 1.0,
+                    Distribution::Gaussian => {
+                        // Clamping piles everything beyond the ends onto the first and last value.
+                        let upper = if value == high { 1.0 } else { normal_cdf((above - middle) / sigma) };
+                        let lower = if value == low { 0.0 } else { normal_cdf((below - middle) / sigma) };
+                        upper - lower
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Distribution::Uniform => "uniform",
+            Distribution::Triangular => "triangular",
+            Distribution::RoundNumbers => "round",
+            Distribution::Gaussian => "gaussian",
+        };
+        f.pad(name) // pad, unlike write!, respects a width like {:>10}.
+    }
+}
+
+pub struct ChiSquare {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    pub p_value: f64,
+}
+
+impl ChiSquare {
+    // Pearson's chi-square goodness-of-fit test. Neighbouring values are pooled until every bin expects at least five samples, the usual rule for the test to be reliable.
+    pub fn test(observed: &[u64], probabilities: &[f64]) -> ChiSquare {
+        let total: u64 = observed.iter().sum();
+        let mut bins = Vec::new();
+        let (mut o, mut e) = (0.0, 0.0);
+        for (&count, &p) in observed.iter().zip(probabilities) {
+            o += count as f64;
+            e += p * total as f64;
+            if e >= 5.0 {
+                bins.push((o, e));
+                (o, e) = (0.0, 0.0);
+            }
+        }
+        // Whatever is left over joins the last bin.
+        match bins.last_mut() {
+            Some(last) => {
+                last.0 += o;
+                last.1 += e;
+            }
+            None => bins.push((o, e)),
+        }
+
+        let statistic = bins.iter().filter(|(_, e)| *e > 0.0).map(|(o, e)| (o - e).powi(2) / e).sum();
+        let degrees_of_freedom = bins.len().saturating_sub(1).max(1);
+        ChiSquare { statistic, degrees_of_freedom, p_value: chi_square_p_value(statistic, degrees_of_freedom) }
+    }
+}
+
+// The probability of a chi-square statistic at least this large if the samples really follow the distribution. The Wilson-Hilferty approximation turns the chi-square distribution into a normal one, which is plenty accurate for a sanity check.
+pub fn chi_square_p_value(statistic: f64, degrees_of_freedom: usize) -> f64 {
+    let k = degrees_of_freedom as f64;
+    let z = ((statistic / k).cbrt() - (1.0 - 2.0 / (9.0 * k))) / (2.0 / (9.0 * k)).sqrt();
+    1.0 - normal_cdf(z)
+}
+
+// check keeps a count and a probability for every value in the range, so wider ranges would need gigabytes. A million values is already far more than a sample of useful size can fill.
+const MAX_VALUES: i64 = 1_000_000;
+
+// Draws many secrets and checks that their frequencies match the distribution.
+pub fn check<R: Rng>(distribution: Distribution, range: &RangeInclusive<u32>, samples: u64, rng: &mut R) -> Result<ChiSquare, String> {
+    let (low, high) = (*range.start() as i64, *range.end() as i64);
+    if high - low + 1 > MAX_VALUES {
+        return Err(format!("check-dist can only tabulate up to {MAX_VALUES} values, but {low}..={high} has {}. Use a narrower --min and --max.", high - low + 1));
+    }
+    let mut counts = vec![0; (high - low + 1) as usize];
+    for _ in 0..samples {
+        let secret = distribution.sample(range, rng);
+        counts[(secret as i64 - low) as usize] += 1;
+    }
+    Ok(ChiSquare::test(&counts, &distribution.probabilities(low, high)))
+}
+
+// The check-dist command. Without --distribution it checks all of them.
+pub fn run_check(options: &Options) {
+    let range = crate::range_from::<u32>(options).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        std::process::exit(2);
+    });
+    let distributions = match options.distribution {
+        Some(distribution) => vec![distribution],
+        None => ALL.to_vec(),
+    };
+
+    println!("Sampling {} secrets between {} and {}.", options.samples, range.start(), range.end());
+    for distribution in distributions {
+        let result = check(distribution, &range, options.samples, &mut rand::thread_rng()).unwrap_or_else(|message| {
+            eprintln!("{message}");
+            std::process::exit(2);
+        });
+        let verdict = if result.p_value < 0.01 { "does NOT fit" } else { "fits" };
+        println!(
+            "{distribution:>10}: chi-square = {:.1} with {} degrees of freedom, p = {:.3}. The sample {verdict}.",
+            result.statistic, result.degrees_of_freedom, result.p_value
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn normal_cdf_matches_tables() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.0) - 0.841345).abs() < 1e-6);
+        assert!((normal_cdf(-1.96) - 0.024998).abs() < 1e-6);
+    }
+
+    #[test]
+    fn probabilities_add_up_to_one() {
+        for distribution in ALL {
+            for (low, high) in [(1, 100), (-7, 3), (11, 19), (5, 5)] {
+                let total: f64 = distribution.probabilities(low, high).iter().sum();
+                assert!((total - 1.0).abs() < 1e-9, "{distribution} over {low}..={high} sums to {total}");
+            }
+        }
+    }
+
+    #[test]
+    fn counts_round_numbers_at_the_ends_of_i64() {
+        assert_eq!(round_numbers(1, 100), (10, 10));
+        assert_eq!(round_numbers(11, 19).1, 0);
+        assert_eq!(round_numbers(i64::MIN, i64::MAX), (-9_223_372_036_854_775_800, 1_844_674_407_370_955_161));
+        assert_eq!(round_numbers(i64::MAX - 5, i64::MAX).1, 0);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for distribution in ALL {
+            for _ in 0..1000 {
+                distribution.sample(&(i64::MIN..=i64::MAX), &mut rng);
+            }
+        }
+    }
+
+    #[test]
+    fn samples_fit_their_distribution() {
+        // A fixed seed makes the samples, and therefore the test, the same on every run.
+        let mut rng = StdRng::seed_from_u64(42);
+        for distribution in ALL {
+            let result = check(distribution, &(1..=100), 50_000, &mut rng).unwrap();
+            assert!(result.p_value > 0.001, "{distribution}: p = {}", result.p_value);
+        }
+    }
+
+    #[test]
+    fn refuses_ranges_too_wide_to_tabulate() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert!(check(Distribution::Uniform, &(1..=MAX_VALUES as u32), 10, &mut rng).is_ok());
+        let error = check(Distribution::Uniform, &(0..=4_000_000_000), 10, &mut rng).err().unwrap();
+        assert_eq!(error, "check-dist can only tabulate up to 1000000 values, but 0..=4000000000 has 4000000001. Use a narrower --min and --max.");
+    }
+
+    #[test]
+    fn detects_a_mismatched_distribution() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let counts = {
+            let mut counts = vec![0; 100];
+            for _ in 0..50_000 {
+                counts[Distribution::Triangular.sample(&(1u32..=100), &mut rng) as usize - 1] += 1;
+            }
+            counts
+        };
+        let result = ChiSquare::test(&counts, &Distribution::Uniform.probabilities(1, 100));
+        assert!(result.p_value < 0.001);
+    }
+}
@@ -0,0 +1,58 @@
+// The Elo rating system, as used in chess. Every player has a rating, and the difference between two ratings predicts how a game between them should go. After the game both ratings move towards what actually happened.
+
+pub const INITIAL_RATING: f64 = 1500.0;
+
+// K decides how far a single game can move a rating. 32 is the value commonly used for new players.
+pub const K_FACTOR: f64 = 32.0;
+
+// The score a player with rating `rating` is expected to get against `opponent`, between 0 and 1. A difference of 400 points means the stronger player is expected to score ten times as much as the weaker one.
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+// score is 1 for a win, 0.5 for a draw and 0 for a loss.
+pub fn new_rating(rating: f64, expected: f64, score: f64) -> f64 {
+    rating + K_FACTOR * (score - expected)
+}
+
+// Updates both players after one game. Whatever one player gains, the other loses.
+pub fn rate_game(a: f64, b: f64, score_a: f64) -> (f64, f64) {
+    let expected_a = expected_score(a, b);
+    (new_rating(a, expected_a, score_a), new_rating(b, 1.0 - expected_a, 1.0 - score_a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 0.001, "{actual} is not close to {expected}");
+    }
+
+    #[test]
+    fn expected_scores() {
+        assert_close(expected_score(1500.0, 1500.0), 0.5);
+        assert_close(expected_score(1900.0, 1500.0), 10.0 / 11.0);
+        assert_close(expected_score(1500.0, 1900.0), 1.0 / 11.0);
+        assert_close(expected_score(1613.0, 1609.0), 0.506);
+    }
+
+    // The example from the Wikipedia article on the Elo rating system: a 1613 player scores 2.5 from five games against opponents rated 1609, 1477, 1388, 1586 and 1720.
+    #[test]
+    fn tournament_example() {
+        let opponents = [1609.0, 1477.0, 1388.0, 1586.0, 1720.0];
+        let expected: f64 = opponents.iter().map(|&o| expected_score(1613.0, o)).sum();
+        assert!((expected - 2.867).abs() < 0.01);
+        assert_eq!(new_rating(1613.0, expected, 2.5).round(), 1601.0);
+    }
+
+    #[test]
+    fn rating_points_are_exchanged() {
+        assert_eq!(rate_game(1500.0, 1500.0, 1.0), (1516.0, 1484.0));
+        assert_eq!(rate_game(1500.0, 1500.0, 0.5), (1500.0, 1500.0));
+
+        let (a, b) = rate_game(1900.0, 1500.0, 0.0);
+        assert_close(a, 1900.0 - 32.0 * 10.0 / 11.0);
+        assert_close(a + b, 3400.0);
+    }
+}
@@ -0,0 +1,297 @@
+// The rules of the game do not care whether the secret is a u32, a negative i64, a float or a letter. All they need is a type that can be parsed from the input, compared to the secret and drawn at random. A trait collects exactly those requirements, and the game is written once, generic over any type that implements it.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Bound, RangeInclusive};
+use std::str::FromStr;
+
+use rand::distributions::uniform::SampleUniform;
+
+use crate::input::{self, ParseError};
+use crate::interval::Interval;
+use rand::Rng; // The Rng trait defines methods that random number generators implement, and this trait must be in scope for us to use those methods.
+
+// The part after the colon lists supertraits: anything that is Guessable must also implement all of these. SampleUniform is rand's trait for types that gen_range can produce.
+pub trait Guessable: Copy + PartialOrd + FromStr + fmt::Display + SampleUniform {
+    // Associated constants belong to the type rather than to a value, e.g. u32::DEFAULT_RANGE.
+    const NAME: &'static str;
+    const DEFAULT_RANGE: (Self, Self);
+
+    // Discrete types count values (1 to 100 are 100 numbers), continuous ones measure length (0.0 to 100.0 is 100 long).
+    const CONTINUOUS: bool = false;
+
+    // Where the value sits on the number line, so that intervals can be measured. from_position goes the other way and rounds to the nearest value.
+    fn position(self) -> f64;
+    fn from_position(position: f64) -> Self;
+
+    // Only floats make use of the tolerance. Exact types ignore it, which is why the parameter is named _tolerance in their impls.
+    fn compare(self, secret: Self, tolerance: f64) -> Ordering;
+
+    // Turns the player's input into a guess. This default method uses FromStr; the integer types override it to accept hex, roman numerals, words and arithmetic.
+    fn parse_guess(input: &str) -> Result<Self, ParseError> {
+        input.parse().map_err(|_| ParseError::new(0, format!("not a valid {}", Self::NAME)))
+    }
+
+    // The bounds for the secret once it is known to be above or below this value. Floats have no next value, so by default the value itself is excluded.
+    fn above(self) -> Bound<Self> {
+        Bound::Excluded(self)
+    }
+
+    fn below(self) -> Bound<Self> {
+        Bound::Excluded(self)
+    }
+}
+
+// Types where every value has a direct neighbour. Returns None at the edges of the type, and for char in the gap where no valid chars exist.
+trait Discrete: Sized {
+    fn next(self) -> Option<Self>;
+    fn previous(self) -> Option<Self>;
+    fn index(self) -> i64;
+    fn from_index(index: i64) -> Self;
+}
+
+impl Discrete for u32 {
+    fn next(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    fn previous(self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    fn index(self) -> i64 {
+        self as i64
+    }
+
+    // clamp keeps the value inside the type before the as cast.
+    fn from_index(index: i64) -> Self {
+        index.clamp(0, u32::MAX as i64) as u32
+    }
+}
+
+impl Discrete for i64 {
+    fn next(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    fn previous(self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    fn index(self) -> i64 {
+        self
+    }
+
+    fn from_index(index: i64) -> Self {
+        index
+    }
+}
+
+impl Discrete for char {
+    fn next(self) -> Option<Self> {
+        char::from_u32(self as u32 + 1)
+    }
+
+    fn previous(self) -> Option<Self> {
+        (self as u32).checked_sub(1).and_then(char::from_u32)
+    }
+
+    fn index(self) -> i64 {
+        self as i64
+    }
+
+    // Falls back to the replacement character for numbers that are not valid chars.
+    fn from_index(index: i64) -> Self {
+        u32::try_from(index).ok().and_then(char::from_u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+}
+
+// A macro saves writing the same impl block for every discrete type. Types that implement Ord never have incomparable values, so cmp is all we need.
+// The optional last argument, $(...)?, names a function that replaces the default parse_guess.
+macro_rules! exact_guessable {
+    ($($t:ty => $name:literal, $low:expr, $high:expr $(, $parse:path)?;)*) => {
+        $(
+            impl Guessable for $t {
+                const NAME: &'static str = $name;
+                const DEFAULT_RANGE: (Self, Self) = ($low, $high);
+
+                fn position(self) -> f64 {
+                    self.index() as f64
+                }
+
+                fn from_position(position: f64) -> Self {
+                    Self::from_index(position.round() as i64)
+                }
+
+                fn compare(self, secret: Self, _tolerance: f64) -> Ordering {
+                    self.cmp(&secret)
+                }
+
+                // Above 40 means 41 or more, so the bound can include the neighbour instead of excluding the guess.
+                fn above(self) -> Bound<Self> {
+                    self.next().map_or(Bound::Excluded(self), Bound::Included)
+                }
+
+                fn below(self) -> Bound<Self> {
+                    self.previous().map_or(Bound::Excluded(self), Bound::Included)
+                }
+
+                $(
+                    fn parse_guess(input: &str) -> Result<Self, ParseError> {
+                        $parse(input)
+                    }
+                )?
+            }
+        )*
+    };
+}
+
+exact_guessable! {
+    u32 => "number", 1, 100, input::parse_integer;
+    i64 => "integer", -100, 100, input::parse_integer;
+    char => "letter", 'a', 'z';
+}
+
+impl Guessable for f64 {
+    const NAME: &'static str = "decimal number";
+    const DEFAULT_RANGE: (Self, Self) = (0.0, 100.0);
+    const CONTINUOUS: bool = true;
+
+    fn position(self) -> f64 {
+        self
+    }
+
+    fn from_position(position: f64) -> Self {
+        position
+    }
+
+    // Floats almost never match exactly, so anything closer than the tolerance counts as a hit. partial_cmp only returns None for NaN, which the game never lets through because NaN is not contained in any range.
+    fn compare(self, secret: Self, tolerance: f64) -> Ordering {
+        if (self - secret).abs() <= tolerance {
+            Ordering::Equal
+        } else {
+            self.partial_cmp(&secret).unwrap_or(Ordering::Less)
+        }
+    }
+}
+
+pub struct Game<T: Guessable> {
+    secret: T,
+    range: RangeInclusive<T>,
+    tolerance: f64,
+    attempts: u32,
+    feasible: Interval<T>,
+    guessed: Vec<T>,
+}
+
+// Why a guess cannot tell the player anything new.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Redundant {
+    Repeated,
+    Infeasible,
+}
+
+impl<T: Guessable> Game<T> {
+    pub fn new(secret: T, range: RangeInclusive<T>, tolerance: f64) -> Game<T> {
+        let feasible = Interval::new(&range);
+        Game { secret, range, tolerance, attempts: 0, feasible, guessed: Vec::new() }
+    }
+
+    pub fn random(range: RangeInclusive<T>, tolerance: f64) -> Game<T> {
+        let secret = rand::thread_rng().gen_range(range.clone()); // rand::thread_rng function gives us a random number generator that is local to the current thread and seeded by the OS. gen_range method generates the random value within the range, both ends inclusive.
+        Game::new(secret, range, tolerance)
+    }
+
+    pub fn secret(&self) -> T {
+        self.secret
+    }
+
+    pub fn range(&self) -> &RangeInclusive<T> {
+        &self.range
+    }
+
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn feasible(&self) -> &Interval<T> {
+        &self.feasible
+    }
+
+    // None for a guess that is worth making. Guesses outside the range are not redundant, guess rejects them anyway.
+    pub fn redundancy(&self, guess: T) -> Option<Redundant> {
+        if !self.range.contains(&guess) {
+            None
+        } else if self.guessed.contains(&guess) {
+            Some(Redundant::Repeated)
+        } else if !self.feasible.contains(guess) {
+            Some(Redundant::Infeasible)
+        } else {
+            None
+        }
+    }
+
+    // Narrows the feasible interval with an answer someone else got, without counting an attempt.
+    pub fn observe(&mut self, guess: T, ordering: Ordering) {
+        self.feasible.narrow(guess, ordering);
+    }
+
+    // Guesses outside the range are turned away without counting as an attempt.
+    pub fn guess(&mut self, guess: T) -> Option<Ordering> {
+        if !self.range.contains(&guess) {
+            return None;
+        }
+        self.attempts += 1;
+        let ordering = guess.compare(self.secret, self.tolerance);
+        self.feasible.narrow(guess, ordering);
+        self.guessed.push(guess);
+        Some(ordering)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floats_match_within_the_tolerance() {
+        // The tolerance itself still counts as a hit, anything past it does not.
+        assert_eq!(10.5.compare(10.0, 0.5), Ordering::Equal);
+        assert_eq!(9.5.compare(10.0, 0.5), Ordering::Equal);
+        assert_eq!(10.5.compare(10.0, 0.25), Ordering::Greater);
+        assert_eq!(9.5.compare(10.0, 0.25), Ordering::Less);
+        assert_eq!(10.000_001.compare(10.0, 0.0), Ordering::Greater);
+        assert_eq!(10.0.compare(10.0, 0.0), Ordering::Equal);
+    }
+
+    #[test]
+    fn plays_with_negative_integers() {
+        let mut game = Game::new(-50i64, -100..=-1, 0.0);
+        assert_eq!(i64::parse_guess("-75"), Ok(-75));
+        assert_eq!(game.guess(-75), Some(Ordering::Less));
+        assert_eq!(game.feasible().to_string(), "between -74 and -1");
+        assert_eq!(game.guess(-25), Some(Ordering::Greater));
+        assert_eq!(game.feasible().to_string(), "between -74 and -26");
+        assert_eq!(game.redundancy(-80), Some(Redundant::Infeasible));
+        assert_eq!(game.guess(0), None);
+        assert_eq!(game.guess(-50), Some(Ordering::Equal));
+        assert_eq!(game.attempts(), 3);
+    }
+
+    #[test]
+    fn chars_stop_at_the_ends_and_the_gap() {
+        assert_eq!('\0'.previous(), None);
+        assert_eq!(char::MAX.next(), None);
+        assert_eq!('a'.next(), Some('b'));
+        // U+D800 to U+DFFF are surrogates, which are not valid chars.
+        assert_eq!('\u{D7FF}'.next(), None);
+        assert_eq!('\u{E000}'.previous(), None);
+        assert_eq!(char::MAX.above(), Bound::Excluded(char::MAX));
+        assert_eq!('\0'.below(), Bound::Excluded('\0'));
+        assert_eq!('m'.above(), Bound::Included('n'));
+    }
+}
@@ -0,0 +1,224 @@
+// The bits of HTTP/1.1 the API needs. A request is a request line ("POST /games HTTP/1.1"), header lines, an empty line and, if a Content-Length header says so, a body. A response has the same shape with a status line instead.
+
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+// Bodies bigger than this are refused instead of being read into memory.
+pub const MAX_BODY: usize = 64 * 1024;
+// The same goes for the request line and each header line, and for the number of headers. Real clients stay far below both.
+pub const MAX_LINE: usize = 8 * 1024;
+pub const MAX_HEADERS: usize = 64;
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    // Header names are case-insensitive, so they are stored in lower case.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers.iter().find(|(n, _)| *n == name).map(|(_, value)| value.as_str())
+    }
+
+    // Reads one request. Ok(None) means the client closed the connection before sending anything.
+    pub fn read<R: BufRead>(reader: &mut R) -> Result<Option<Request>, RequestError> {
+        let mut line = String::new();
+        if read_line(reader, &mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target), Some(version), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(RequestError::Malformed(format!("bad request line {:?}", line.trim_end())));
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(RequestError::Malformed(format!("unsupported version {version}")));
+        }
+        // The query string is not used by any route.
+        let path = target.split('?').next().unwrap_or(target).to_string();
+        let method = method.to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            if read_line(reader, &mut line)? == 0 {
+                return Err(RequestError::Malformed("the headers never ended".to_string()));
+            }
+            let header = line.trim_end_matches(['\r', '\n']);
+            if header.is_empty() {
+                break;
+            }
+            if headers.len() == MAX_HEADERS {
+                return Err(RequestError::HeadersTooLarge);
+            }
+            let Some((name, value)) = header.split_once(':') else {
+                return Err(RequestError::Malformed(format!("bad header {header:?}")));
+            };
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+
+        let mut request = Request { method, path, headers, body: String::new() };
+        let length = match request.header("content-length") {
+            Some(length) => length.parse::<usize>().map_err(|_| RequestError::Malformed(format!("bad Content-Length {length:?}")))?,
+            None => 0,
+        };
+        if length > MAX_BODY {
+            return Err(RequestError::TooLarge);
+        }
+
+        // read_exact fills the whole buffer or fails, so a body cut short is an error instead of a half request.
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        request.body = String::from_utf8(body).map_err(|_| RequestError::Malformed("the body is not UTF-8".to_string()))?;
+        Ok(Some(request))
+    }
+
+    // The path split at the slashes, so routes can be matched with slice patterns like ["games", id].
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+}
+
+// read_line on its own keeps reading until it finds a newline, however long the line gets. take stops it after MAX_LINE bytes, and a line that has not ended by then is refused.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String) -> Result<usize, RequestError> {
+    let read = reader.take(MAX_LINE as u64).read_line(line)?;
+    if read == MAX_LINE && !line.ends_with('\n') {
+        return Err(RequestError::HeadersTooLarge);
+    }
+    Ok(read)
+}
+
+#[derive(Debug)]
+pub enum RequestError {
+    Io(io::Error),
+    Malformed(String),
+    TooLarge,
+    HeadersTooLarge,
+}
+
+// The ? operator calls From to turn an io::Error into a RequestError.
+impl From<io::Error> for RequestError {
+    fn from(error: io::Error) -> RequestError {
+        RequestError::Io(error)
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RequestError::Io(error) => write!(f, "{error}"),
+            RequestError::Malformed(message) => write!(f, "{message}"),
+            RequestError::TooLarge => write!(f, "the body is larger than {MAX_BODY} bytes"),
+            RequestError::HeadersTooLarge => write!(f, "a line is longer than {MAX_LINE} bytes, or there are more than {MAX_HEADERS} headers"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: String) -> Response {
+        Response { status, body }
+    }
+
+    pub fn error(status: u16, message: &str) -> Response {
+        Response::json(status, format!("{{\"error\":{}}}", crate::json::string(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+
+    // Every response closes the connection, so the client knows the body ends where the stream does. Keep-alive would leave the server waiting for a next request that may never come.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // &[u8] implements BufRead, so a byte string can stand in for a network connection.
+    fn read(text: &str) -> Result<Option<Request>, RequestError> {
+        Request::read(&mut text.as_bytes())
+    }
+
+    #[test]
+    fn parses_a_request_with_a_body() {
+        let request = read("POST /games/7/guesses?x=1 HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nCONTENT-LENGTH: 13\r\n\r\n{\"guess\": 50}").unwrap().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/games/7/guesses");
+        assert_eq!(request.segments(), ["games", "7", "guesses"]);
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(request.header("Host"), Some("localhost"));
+        assert_eq!(request.body, "{\"guess\": 50}");
+    }
+
+    #[test]
+    fn parses_a_request_without_a_body() {
+        let request = read("GET /games/1 HTTP/1.0\n\n").unwrap().unwrap();
+        assert_eq!((request.method.as_str(), request.path.as_str(), request.body.as_str()), ("GET", "/games/1", ""));
+        assert!(read("").unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        assert!(matches!(read("GET /games\r\n\r\n"), Err(RequestError::Malformed(_))));
+        assert!(matches!(read("GET /games SPDY/3\r\n\r\n"), Err(RequestError::Malformed(_))));
+        assert!(matches!(read("GET / HTTP/1.1\r\nno colon\r\n\r\n"), Err(RequestError::Malformed(_))));
+        assert!(matches!(read("GET / HTTP/1.1\r\nHost: x\r\n"), Err(RequestError::Malformed(_))));
+        assert!(matches!(read("POST / HTTP/1.1\r\nContent-Length: ten\r\n\r\n"), Err(RequestError::Malformed(_))));
+        assert!(matches!(read("POST / HTTP/1.1\r\nContent-Length: 99999999\r\n\r\n"), Err(RequestError::TooLarge)));
+        assert!(matches!(read("POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort"), Err(RequestError::Io(_))));
+    }
+
+    #[test]
+    fn limits_the_headers() {
+        let long = format!("GET / HTTP/1.1\r\nCookie: {}\r\n\r\n", "x".repeat(MAX_LINE));
+        assert!(matches!(read(&long), Err(RequestError::HeadersTooLarge)));
+        let endless = format!("GET /{}", "x".repeat(10 * MAX_LINE));
+        assert!(matches!(read(&endless), Err(RequestError::HeadersTooLarge)));
+
+        let headers = |count: usize| format!("GET / HTTP/1.1\r\n{}\r\n", "X: y\r\n".repeat(count));
+        assert!(read(&headers(MAX_HEADERS)).unwrap().is_some());
+        assert!(matches!(read(&headers(MAX_HEADERS + 1)), Err(RequestError::HeadersTooLarge)));
+    }
+
+    #[test]
+    fn writes_a_response() {
+        let mut out = Vec::new();
+        Response::error(404, "no such game").write_to(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: 24\r\nConnection: close\r\n\r\n{\"error\":\"no such game\"}"
+        );
+    }
+}
@@ -0,0 +1,361 @@
+// Turns what the player typed into a number. Besides plain decimals it understands hexadecimal (0x2a), binary (0b101010), octal (0o52), roman numerals (XLII), English number words (forty-two) and arithmetic with + - * / % and parentheses (50+25/2).
+
+// Evaluation works in two steps. The lexer cuts the input into tokens, and a recursive descent parser computes the value while it reads them. Each grammar rule becomes one function:
+//
+// expression := term (('+' | '-') term)*
+// term       := factor (('*' | '/' | '%') factor)*
+// factor     := '-' factor | primary
+// primary    := number | word+ | '(' expression ')'
+
+use std::fmt;
+
+// Positions count characters from the start of the input, so the caret in an error message lines up under the right character.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(position: usize, message: impl Into<String>) -> ParseError {
+        ParseError { position, message: message.into() }
+    }
+
+    // Shows the input with a caret under the offending character.
+    pub fn render(&self, input: &str) -> String {
+        format!("{}\n  {input}\n  {}^", self.message, " ".repeat(self.position))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position + 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Word(String),
+    Operator(char),
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push((start, Token::Number(parse_literal(&text, start)?)));
+        } else if c.is_alphabetic() {
+            // A hyphen between two letters belongs to the word, as in forty-two.
+            while i < chars.len()
+                && (chars[i].is_alphabetic()
+                    || (chars[i] == '-' && chars.get(i + 1).is_some_and(|n| n.is_alphabetic())))
+            {
+                i += 1;
+            }
+            tokens.push((start, Token::Word(chars[start..i].iter().collect())));
+        } else {
+            let token = match c {
+                '+' | '-' | '*' | '/' | '%' => Token::Operator(c),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                _ => return Err(ParseError::new(start, format!("unexpected character '{c}'"))),
+            };
+            tokens.push((start, token));
+            i += 1;
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_literal(text: &str, position: usize) -> Result<i64, ParseError> {
+    let digits = text.replace('_', "");
+    // from_str_radix parses digits in any base from 2 to 36.
+    let parsed = match digits.get(..2) {
+        Some("0x") | Some("0X") => i64::from_str_radix(&digits[2..], 16),
+        Some("0b") | Some("0B") => i64::from_str_radix(&digits[2..], 2),
+        Some("0o") | Some("0O") => i64::from_str_radix(&digits[2..], 8),
+        _ => digits.parse(),
+    };
+    parsed.map_err(|error| ParseError::new(position, format!("`{text}` is not a valid number ({error})")))
+}
+
+const ROMAN: [(&str, i64); 13] = [
+    ("M", 1000), ("CM", 900), ("D", 500), ("CD", 400),
+    ("C", 100), ("XC", 90), ("L", 50), ("XL", 40),
+    ("X", 10), ("IX", 9), ("V", 5), ("IV", 4), ("I", 1),
+];
+
+pub fn to_roman(mut value: i64) -> String {
+    let mut roman = String::new();
+    for (symbol, amount) in ROMAN {
+        while value >= amount {
+            roman.push_str(symbol);
+            value -= amount;
+        }
+    }
+    roman
+}
+
+// Reads greedily from the largest symbol down, then checks the result by writing it back. That rejects non-canonical forms such as IIII or VX.
+fn parse_roman(word: &str) -> Option<i64> {
+    let mut rest = word;
+    let mut value = 0;
+    for (symbol, amount) in ROMAN {
+        while let Some(stripped) = rest.strip_prefix(symbol) {
+            value += amount;
+            rest = stripped;
+        }
+    }
+    (rest.is_empty() && value > 0 && to_roman(value) == word).then_some(value)
+}
+
+const UNITS: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const TENS: [&str; 8] = ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+// Number words are summed up within a group of hundreds, and every scale word (thousand, million) closes a group.
+fn parse_words(words: &[String]) -> Result<i64, String> {
+    let mut total = 0;
+    let mut group = 0;
+    let mut previous = "";
+
+    for word in words.iter().flat_map(|w| w.split('-')) {
+        let word = word.to_lowercase();
+        if let Some(value) = UNITS.iter().position(|&u| u == word) {
+            if matches!(previous, "unit") {
+                return Err(format!("`{word}` cannot follow another small number"));
+            }
+            group += value as i64;
+            previous = "unit";
+        } else if let Some(index) = TENS.iter().position(|&t| t == word) {
+            if matches!(previous, "unit" | "tens") {
+                return Err(format!("`{word}` cannot follow another number"));
+            }
+            group += (index as i64 + 2) * 10;
+            previous = "tens";
+        } else if word == "hundred" {
+            if group == 0 || group >= 100 {
+                return Err("`hundred` needs a number from one to ninety-nine before it".to_string());
+            }
+            group *= 100;
+            previous = "hundred";
+        } else if let Some(scale) = match word.as_str() {
+            "thousand" => Some(1_000),
+            "million" => Some(1_000_000),
+            _ => None,
+        } {
+            if group == 0 {
+                return Err(format!("`{word}` needs a number before it"));
+            }
+            total += group * scale;
+            group = 0;
+            previous = "scale";
+        } else if word == "and" && !previous.is_empty() {
+            continue;
+        } else {
+            return Err(format!("`{word}` is not a number word or roman numeral"));
+        }
+    }
+
+    Ok(total + group)
+}
+
+// Every '-' and '(' makes the parser call itself once more. Without a limit, input like 200000 minus signs would overflow the stack and abort the game instead of being rejected as a typo.
+const MAX_NESTING: usize = 64;
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    end: usize,
+    // How many '-' and '(' the parser is inside of, see MAX_NESTING.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.next).map_or(self.end, |(position, _)| *position)
+    }
+
+    // Goes one level deeper. The callers put self.depth back when they are done with the level.
+    fn nest(&mut self, position: usize) -> Result<(), ParseError> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err(ParseError::new(position, "the expression is nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self) -> Result<i64, ParseError> {
+        let mut value = self.term()?;
+        while let Some(Token::Operator(op @ ('+' | '-'))) = self.peek() {
+            let (op, position) = (*op, self.position());
+            self.next += 1;
+            let right = self.term()?;
+            value = apply(op, value, right, position)?;
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<i64, ParseError> {
+        let mut value = self.factor()?;
+        while let Some(Token::Operator(op @ ('*' | '/' | '%'))) = self.peek() {
+            let (op, position) = (*op, self.position());
+            self.next += 1;
+            let right = self.factor()?;
+            value = apply(op, value, right, position)?;
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<i64, ParseError> {
+        if let Some(Token::Operator('-')) = self.peek() {
+            let position = self.position();
+            self.next += 1;
+            self.nest(position)?;
+            let value = self.factor()?;
+            self.depth -= 1;
+            return value.checked_neg().ok_or(ParseError::new(position, "the result is too large"));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<i64, ParseError> {
+        let position = self.position();
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.next += 1;
+                Ok(value)
+            }
+            Some(Token::Word(_)) => {
+                let mut words = Vec::new();
+                while let Some(Token::Word(word)) = self.peek() {
+                    words.push(word.clone());
+                    self.next += 1;
+                }
+                if let [word] = words.as_slice()
+                    && let Some(value) = parse_roman(word)
+                {
+                    return Ok(value);
+                }
+                parse_words(&words).map_err(|message| ParseError::new(position, message))
+            }
+            Some(Token::Open) => {
+                self.next += 1;
+                self.nest(position)?;
+                let value = self.expression()?;
+                self.depth -= 1;
+                match self.peek() {
+                    Some(Token::Close) => {
+                        self.next += 1;
+                        Ok(value)
+                    }
+                    _ => Err(ParseError::new(self.position(), "expected ')'")),
+                }
+            }
+            Some(_) => Err(ParseError::new(position, "expected a number")),
+            None => Err(ParseError::new(position, "expected a number, but the input ended")),
+        }
+    }
+}
+
+// Integer division truncates toward zero, exactly like Rust's / on integers. Checked arithmetic turns overflow and division by zero into errors instead of panics.
+fn apply(op: char, left: i64, right: i64, position: usize) -> Result<i64, ParseError> {
+    let result = match op {
+        '+' => left.checked_add(right),
+        '-' => left.checked_sub(right),
+        '*' => left.checked_mul(right),
+        '/' | '%' if right == 0 => return Err(ParseError::new(position, "division by zero")),
+        '/' => left.checked_div(right),
+        '%' => left.checked_rem(right),
+        _ => unreachable!("the parser only hands over arithmetic operators"),
+    };
+    result.ok_or(ParseError::new(position, "the result is too large"))
+}
+
+pub fn evaluate(input: &str) -> Result<i64, ParseError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, next: 0, end: input.chars().count(), depth: 0 };
+    let value = parser.expression()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(Token::Close) => Err(ParseError::new(parser.position(), "unmatched ')'")),
+        Some(_) => Err(ParseError::new(parser.position(), "expected an operator")),
+    }
+}
+
+// Evaluates the input and converts the result to the integer type of the game. TryFrom fails for values that do not fit, like -5 for a u32.
+pub fn parse_integer<T: TryFrom<i64>>(input: &str) -> Result<T, ParseError> {
+    let value = evaluate(input)?;
+    T::try_from(value).map_err(|_| ParseError::new(0, format!("{value} is out of range")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_every_syntax() {
+        assert_eq!(evaluate("42"), Ok(42));
+        assert_eq!(evaluate("0x2a"), Ok(42));
+        assert_eq!(evaluate("0b101010"), Ok(42));
+        assert_eq!(evaluate("0o52"), Ok(42));
+        assert_eq!(evaluate("XLII"), Ok(42));
+        assert_eq!(evaluate("forty-two"), Ok(42));
+        assert_eq!(evaluate("one hundred and five"), Ok(105));
+        assert_eq!(evaluate("two thousand three hundred"), Ok(2300));
+        assert_eq!(evaluate("50+25/2"), Ok(62));
+        assert_eq!(evaluate("-(3 - 10) * 2 % 5"), Ok(4));
+        assert_eq!(evaluate("X + ten"), Ok(20));
+    }
+
+    #[test]
+    fn reports_positions() {
+        assert_eq!(evaluate("50+*2").unwrap_err().position, 3);
+        assert_eq!(evaluate("(1+2").unwrap_err().position, 4);
+        assert_eq!(evaluate("1+2)").unwrap_err().position, 3);
+        assert_eq!(evaluate("7 / (2-2)").unwrap_err().position, 2);
+        assert_eq!(evaluate("3 $").unwrap_err().position, 2);
+        assert_eq!(evaluate("1 + IIII").unwrap_err().position, 4);
+        assert_eq!(evaluate("12ab").unwrap_err().position, 0);
+        assert_eq!(evaluate("two three").unwrap_err().position, 0);
+    }
+
+    #[test]
+    fn converts_to_the_game_type() {
+        assert_eq!(parse_integer::<u32>("100 - 1"), Ok(99));
+        assert!(parse_integer::<u32>("1 - 2").is_err());
+        assert_eq!(parse_integer::<i64>("1 - 2"), Ok(-1));
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(evaluate(&nested(MAX_NESTING)), Ok(1));
+        assert_eq!(evaluate(&nested(MAX_NESTING + 1)), Err(ParseError::new(MAX_NESTING, "the expression is nested too deeply")));
+        assert_eq!(evaluate(&format!("{}1", "-".repeat(MAX_NESTING))), Ok(1));
+        assert_eq!(evaluate(&format!("{}1", "-".repeat(200_000))).unwrap_err().message, "the expression is nested too deeply");
+        assert_eq!(evaluate(&format!("{}1", "(".repeat(30_000))).unwrap_err().position, MAX_NESTING);
+    }
+}
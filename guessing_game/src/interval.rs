@@ -0,0 +1,120 @@
+// Every "Too small!" and "Too big!" rules out part of the range. The interval is what is left: the values the secret can still be.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+use crate::game::Guessable;
+
+// Bound comes from the standard library. Included(41) means 41 is still possible, Excluded(40.5) means everything above 40.5 but not 40.5 itself. Floats need the second kind because there is no "next" float after a guess.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval<T> {
+    pub low: Bound<T>,
+    pub high: Bound<T>,
+}
+
+fn value<T: Copy>(bound: Bound<T>) -> T {
+    match bound {
+        Bound::Included(v) | Bound::Excluded(v) => v,
+        Bound::Unbounded => unreachable!("intervals always have both ends"),
+    }
+}
+
+impl<T: Guessable> Interval<T> {
+    pub fn new(range: &RangeInclusive<T>) -> Interval<T> {
+        Interval { low: Bound::Included(*range.start()), high: Bound::Included(*range.end()) }
+    }
+
+    pub fn low(&self) -> T {
+        value(self.low)
+    }
+
+    pub fn high(&self) -> T {
+        value(self.high)
+    }
+
+    // How many values are left, or for floats how long the interval is. An empty interval has size 0.
+    pub fn size(&self) -> f64 {
+        let length = self.high().position() - self.low().position();
+        let size = if T::CONTINUOUS { length } else { length + 1.0 };
+        size.max(0.0)
+    }
+
+    // A tuple of two bounds implements RangeBounds, which provides contains for free.
+    pub fn contains(&self, value: T) -> bool {
+        (self.low, self.high).contains(&value)
+    }
+
+    // Moves one end of the interval past the guess. Only ever makes the interval smaller, so a guess outside of it changes nothing.
+    pub fn narrow(&mut self, guess: T, ordering: Ordering) {
+        match ordering {
+            Ordering::Less => {
+                let low = guess.above();
+                if Interval::tighter(low, self.low, Ordering::Greater) {
+                    self.low = low;
+                }
+            }
+            Ordering::Greater => {
+                let high = guess.below();
+                if Interval::tighter(high, self.high, Ordering::Less) {
+                    self.high = high;
+                }
+            }
+            Ordering::Equal => {}
+        }
+    }
+
+    // `inward` is the direction in which a bound moves to make the interval smaller. At the same value an excluding bound is tighter than an including one.
+    fn tighter(new: Bound<T>, old: Bound<T>, inward: Ordering) -> bool {
+        match value(new).partial_cmp(&value(old)) {
+            Some(Ordering::Equal) => matches!((new, old), (Bound::Excluded(_), Bound::Included(_))),
+            ordering => ordering == Some(inward),
+        }
+    }
+}
+
+impl<T: fmt::Display + Copy + PartialEq> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.low, self.high) {
+            (Bound::Included(low), Bound::Included(high)) if low == high => write!(f, "exactly {low}"),
+            (Bound::Included(low), Bound::Included(high)) => write!(f, "between {low} and {high}"),
+            (low, high) => {
+                match low {
+                    Bound::Included(v) => write!(f, "at least {v}")?,
+                    _ => write!(f, "above {}", value(low))?,
+                }
+                match high {
+                    Bound::Included(v) => write!(f, " and at most {v}"),
+                    _ => write!(f, " and below {}", value(high)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn narrows_integers_to_inclusive_bounds() {
+        let mut interval = Interval::new(&(1u32..=100));
+        interval.narrow(40, Ordering::Less);
+        interval.narrow(60, Ordering::Greater);
+        assert_eq!(interval.to_string(), "between 41 and 59");
+
+        // Guesses that are already ruled out do not widen the interval again.
+        interval.narrow(10, Ordering::Less);
+        interval.narrow(90, Ordering::Greater);
+        assert_eq!(interval.to_string(), "between 41 and 59");
+        assert!(interval.contains(41) && !interval.contains(40));
+    }
+
+    #[test]
+    fn narrows_floats_to_exclusive_bounds() {
+        let mut interval = Interval::new(&(0.0..=100.0));
+        interval.narrow(40.5, Ordering::Less);
+        assert_eq!(interval.to_string(), "above 40.5 and at most 100");
+        assert!(!interval.contains(40.5) && interval.contains(40.6));
+    }
+}
@@ -0,0 +1,254 @@
+// Just enough JSON for the HTTP API: a parser for request bodies and escaping for the responses, which are written with format!. A crate like serde_json would do all of this and more.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    // Numbers are kept as the text they were written with, so 50 stays 50 instead of becoming 50.0.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.get(key),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.position)
+    }
+}
+
+// Arrays and objects inside each other deeper than this are rejected. Every level is a recursive call, and without a limit a body of thousands of [ would overflow the stack and take the whole server down.
+const MAX_DEPTH: usize = 64;
+
+// A recursive descent parser, the same idea as input.rs: one method per kind of value, each consuming the bytes it understands.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    // How many arrays and objects are open at the current position.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error<T>(&self, message: &str) -> Result<T, Error> {
+        Err(Error { position: self.position, message: message.to_string() })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.bytes.get(self.position) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), Error> {
+        if self.bytes[self.position..].starts_with(text.as_bytes()) {
+            self.position += text.len();
+            Ok(())
+        } else {
+            self.error(&format!("expected `{text}`"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        self.skip_whitespace();
+        if self.depth >= MAX_DEPTH && matches!(self.bytes.get(self.position), Some(b'[' | b'{')) {
+            return self.error(&format!("nested deeper than {MAX_DEPTH} levels"));
+        }
+        match self.bytes.get(self.position) {
+            Some(b'n') => self.expect("null").map(|_| Value::Null),
+            Some(b't') => self.expect("true").map(|_| Value::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[' | b'{') => {
+                self.depth += 1;
+                let value = if self.bytes[self.position] == b'[' { self.array() } else { self.object() };
+                self.depth -= 1;
+                value
+            }
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => self.error("unexpected character"),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, Error> {
+        let start = self.position;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.bytes.get(self.position) {
+            self.position += 1;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.position]).expect("only ASCII was consumed");
+        if text.parse::<f64>().is_err() {
+            self.position = start;
+            return self.error("invalid number");
+        }
+        Ok(Value::Number(text.to_string()))
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.expect("\"")?;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&byte) = self.bytes.get(self.position) else {
+                return self.error("unterminated string");
+            };
+            self.position += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&escaped) = self.bytes.get(self.position) else {
+                        return self.error("unterminated string");
+                    };
+                    self.position += 1;
+                    let c = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'n' => '\n',
+                        b't' => '\t',
+                        b'r' => '\r',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'u' => {
+                            let hex = self.bytes.get(self.position..self.position + 4).and_then(|hex| std::str::from_utf8(hex).ok());
+                            match hex.and_then(|hex| u32::from_str_radix(hex, 16).ok()).and_then(char::from_u32) {
+                                Some(c) => {
+                                    self.position += 4;
+                                    c
+                                }
+                                None => return self.error("invalid \\u escape"),
+                            }
+                        }
+                        _ => return self.error("invalid escape"),
+                    };
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                _ => bytes.push(byte),
+            }
+        }
+        // The input was a &str, so the bytes between the quotes are valid UTF-8 too.
+        Ok(String::from_utf8(bytes).expect("the input was valid UTF-8"))
+    }
+
+    fn array(&mut self) -> Result<Value, Error> {
+        self.expect("[")?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.expect("]").is_ok() {
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            if self.expect(",").is_err() {
+                self.expect("]")?;
+                return Ok(Value::Array(items));
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, Error> {
+        self.expect("{")?;
+        let mut fields = BTreeMap::new();
+        self.skip_whitespace();
+        if self.expect("}").is_ok() {
+            return Ok(Value::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(":")?;
+            fields.insert(key, self.value()?);
+            self.skip_whitespace();
+            if self.expect(",").is_err() {
+                self.expect("}")?;
+                return Ok(Value::Object(fields));
+            }
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Value, Error> {
+    let mut parser = Parser { bytes: text.as_bytes(), position: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position < text.len() {
+        return parser.error("unexpected text after the value");
+    }
+    Ok(value)
+}
+
+// Wraps text in quotes, escaping what JSON does not allow inside a string.
+pub fn string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\r' => quoted += "\\r",
+            '\t' => quoted += "\\t",
+            c if (c as u32) < 0x20 => quoted += &format!("\\u{:04x}", c as u32),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_values() {
+        let value = parse(r#" {"guess": 50, "name": "a\"bA", "list": [true, null, -1.5e3], "empty": {}} "#).unwrap();
+        assert_eq!(value.get("guess"), Some(&Value::Number("50".to_string())));
+        assert_eq!(value.get("name"), Some(&Value::String("a\"bA".to_string())));
+        assert_eq!(
+            value.get("list"),
+            Some(&Value::Array(vec![Value::Bool(true), Value::Null, Value::Number("-1.5e3".to_string())]))
+        );
+        assert_eq!(value.get("empty"), Some(&Value::Object(BTreeMap::new())));
+    }
+
+    #[test]
+    fn reports_where_parsing_failed() {
+        assert_eq!(parse("{\"guess\" 50}").unwrap_err().position, 9);
+        assert_eq!(parse("[1, 2").unwrap_err().message, "expected `]`");
+        assert!(parse("\"open").is_err());
+        assert!(parse("1 2").is_err());
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(parse(&nested(MAX_DEPTH + 1)).unwrap_err(), Error { position: MAX_DEPTH, message: "nested deeper than 64 levels".to_string() });
+        assert!(parse(&"[".repeat(60_000)).is_err());
+        assert!(parse(&"{\"a\":".repeat(1000)).is_err());
+    }
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(string("say \"hi\"\n"), r#""say \"hi\"\n""#);
+        assert_eq!(parse(&string("tab\tand\u{1}")).unwrap(), Value::String("tab\tand\u{1}".to_string()));
+    }
+}
@@ -1,17 +1,144 @@
 use std::io;
-use rand::Rng; // The Rng trait defines methods that random number generators implement, and this trait must be in scope for us to use those methods.
 use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use distribution::Distribution;
+use game::Guessable;
+use options::{Command, Kind, Options};
+use record::GameRecord;
+use timer::{Clock, SystemClock};
+use turn::State;
+
+mod achievements;
+mod analysis;
+mod bot;
+mod bulls_and_cows; // mod declares a module. Rust looks for its code in src/bulls_and_cows.rs.
+mod concepts;
+mod distribution;
+mod elo;
+mod game;
+mod http;
+mod input;
+mod interval;
+mod json;
+mod match_play;
+mod observer;
+mod options;
+mod race;
+mod ratings;
+mod record;
+mod results;
+mod server;
+mod storage;
+mod timer;
+mod tui;
+mod turn;
 
 // By default, Rust has a set of items defined in the standard library that it brings into the scope of every program. This set is called the prelude.
 // If a type you want to use isn’t in the prelude, you have to bring that type into scope explicitly with a use statement. Using the std::io library provides you with a number of useful features, including the ability to accept user input.
 
 fn main() {
-    println!("Guess the number!");
+    let options = Options::from_args();
+
+    match options.command {
+        Command::Bulls => bulls_and_cows::play(),
+        Command::BullsSolve => bulls_and_cows::solve(),
+        Command::Match(ref players) => match options.kind {
+            Kind::U32 => match_play::run::<u32>(&options, players),
+            Kind::I64 => match_play::run::<i64>(&options, players),
+            Kind::F64 => match_play::run::<f64>(&options, players),
+            Kind::Char => match_play::run::<char>(&options, players),
+        },
+        Command::Ratings => match_play::show_ratings(),
+        Command::CheckDist => distribution::run_check(&options),
+        Command::Race => match options.kind {
+            Kind::U32 => race::run::<u32>(&options),
+            Kind::I64 => race::run::<i64>(&options),
+            Kind::F64 => race::run::<f64>(&options),
+            Kind::Char => race::run::<char>(&options),
+        },
+        Command::Achievements => achievements::show(&options.player),
+        Command::Stats => results::show(&options.player),
+        Command::Serve => server::run(&options),
+        Command::Play => {
+            // The turbofish ::<> picks the type parameter explicitly. Each call is compiled into its own copy of run_game, specialized for that type. This is called monomorphization.
+            let record = match options.kind {
+                Kind::U32 => run_game::<u32>(&options),
+                Kind::I64 => run_game::<i64>(&options),
+                Kind::F64 => run_game::<f64>(&options),
+                Kind::Char => run_game::<char>(&options),
+            };
+            achievements::record(&options.player, &record.events);
+            results::record(&options.player, &record);
+        }
+    }
+}
+
+// Reads the bounds given on the command line as values of type T, falling back to the type's default range.
+fn range_from<T: Guessable>(options: &Options) -> Result<RangeInclusive<T>, String> {
+    let (default_min, default_max) = T::DEFAULT_RANGE;
+    let parse = |bound: &Option<String>, default: T| match bound {
+        Some(text) => text.trim().parse::<T>().map_err(|_| format!("`{text}` is not a valid {}", T::NAME)),
+        None => Ok(default),
+    };
+
+    let min = parse(&options.min, default_min)?;
+    let max = parse(&options.max, default_max)?;
+    // Error messages repeat the bounds as they were typed. Display would write 1e308 out with all of its 309 digits.
+    let text = |bound: &Option<String>, value: T| bound.as_deref().map_or(value.to_string(), |text| text.trim().to_string());
+    let (min_text, max_text) = (text(&options.min, min), text(&options.max, max));
+    // partial_cmp returns None when the values cannot be compared at all, e.g. a NaN bound.
+    if !matches!(min.partial_cmp(&max), Some(Ordering::Less | Ordering::Equal)) {
+        return Err(format!("the range {min_text} to {max_text} is empty"));
+    }
+    // gen_range panics when high - low does not fit in an f64, which happens for infinite bounds and for finite ones as far apart as -1e308 and 1e308.
+    if !(max.position() - min.position()).is_finite() {
+        return Err(format!("the range {min_text} to {max_text} is too wide, the bounds and the distance between them must be finite"));
+    }
+    Ok(min..=max)
+}
+
+// The range from the command line and a secret drawn from it with the chosen distribution. Every front end starts a game this way, and an invalid range ends the program before anything is drawn.
+fn pick_secret<T: Guessable>(options: &Options) -> (RangeInclusive<T>, T) {
+    let range = match range_from::<T>(options) {
+        Ok(range) => range,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
+    let distribution = options.distribution.unwrap_or(Distribution::Uniform);
+    let secret = distribution.sample(&range, &mut rand::thread_rng());
+    (range, secret)
+}
 
-    let secret_number = rand::thread_rng().gen_range(1..=100); // rand::thread_rng function gives us a random number generator that is local to the current thread and seeded by the OS. gen_range method generates the random number via the range inputed, between 1 - 100 inclusive.
+// Picks the front end: the full-screen one if it was asked for and the terminal supports it, the text one otherwise.
+fn run_game<T: Guessable>(options: &Options) -> GameRecord {
+    if options.tui {
+        if tui::available() {
+            return tui::play::<T>(options);
+        }
+        eprintln!("The terminal UI needs a terminal, playing in text mode instead.");
+    }
+    play::<T, _>(options, SystemClock)
+}
+
+// T is a type parameter. Inside the function we can only use what the Guessable bound promises: parsing, comparing, displaying and sampling.
+// The clock is a parameter too, so the timing can be driven by a fake clock instead of the real one.
+fn play<T: Guessable, C: Clock>(options: &Options, clock: C) -> GameRecord {
+    let (range, secret) = pick_secret::<T>(options);
+
+    println!("Guess the {}! It is between {} and {}.", T::NAME, range.start(), range.end());
 
-    loop {
-        println!("Please input your guess.");
+    if let Some(limit) = options.time_limit {
+        println!("Time attack! You have {} seconds.", limit.as_secs());
+    }
+
+    let mut state = State::start(secret, range, options, clock);
+
+    // A loop is an expression, and break hands the outcome out of it.
+    let outcome = loop {
+        turn::print(&state.prompt());
 
         // let is used to create a variable. For example, let apple = 5; creates a variable named apple and sets its value to 5. By default, variables are immutable, meaning that once a value is assigned to a variable name, you can’t change that value. To make a variable mutable, you can use the mut keyword before the variable name.
 
@@ -22,23 +149,18 @@ fn main() {
             .read_line(&mut guess) // We call the read_line method on the standard input handle. The full job of read_line is to take whatever the user types into standard input and append that into a string (without overwriting its contents), so we therefore pass that string as an argument. The string argument needs to be mutable so the method can change the string’s content. The & indicates that this argument is a reference, which gives you a way to let multiple parts of your code access one piece of data without needing to copy that data into memory multiple times. References are immutable by default. Hence, you need to write &mut guess rather than &guess to make it mutable.
             .expect("Failed to read line"); // read_line returns a Result value, which is an enum that can have two possible states: Ok and Err. This is the result pattern from functional programming which can be utilized in C# via libraries like LanguageCore.Ext. Result has methods defined on the type, such as expect(...). If the value of Result is Err, expect will cause the program to crash and display the message passed as an arg. If the Result is Ok, expect will just return the value, i.e., the number of bytes in the user input.
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue, // we want to match all Err values, no matter what information they have inside them. continue, which tells the program to go to the next iteration of the loop and ask for another guess.
-        }; // Rust allows us to shadow the previous value of guess with a new one. Shadowing lets us reuse the guess variable name rather than forcing us to create two unique variables, such as guess_str and guess.
-
-        println!("You guessed: {guess}"); // {} is a placeholder. If you want to print the result of an expression, you can do this: println!("x = {x} and y + 2 = {}", y + 2);
-
-        // A match expression is made up of arms. An arm consists of a pattern to match against, and the code that should be run if the value given to match fits that arm’s pattern.
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
-            },
+        // Parsing, comparing and everything else a guess leads to is in src/turn.rs, shared with the full-screen mode.
+        let turn = state.turn(&guess);
+        turn::print(&turn.lines);
+        if let Some(outcome) = turn.outcome {
+            break outcome;
         }
-    }
+    };
+
+    let (lines, record) = state.finish(outcome);
+    turn::print(&lines);
+    println!("{record}");
+    record
 }
 
 // Crates are a collection of Rust source code files. There are two types of crates: binary executables, e.g., this project, and, library crates, which contain code intended to be used by other programs. Crates are similar to Nuget in C#, but not quite the same.
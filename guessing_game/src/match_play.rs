@@ -0,0 +1,141 @@
+// A match is a series of rounds between two players. In every round each player gets a fresh secret, and whoever finds theirs in fewer guesses wins the round. The match is over once one player is so far ahead that the other cannot catch up anymore.
+
+use crate::achievements;
+use crate::bot;
+use crate::game::{Game, Guessable};
+use crate::options::Options;
+use crate::ratings::Ratings;
+use crate::record::{GameRecord, Outcome};
+use crate::results;
+use crate::timer::SystemClock;
+
+// Giving up or running out of time loses against any win.
+fn round_score(a: &GameRecord, b: &GameRecord) -> f64 {
+    let attempts = |record: &GameRecord| (record.outcome == Outcome::Won).then_some(record.attempts());
+    match (attempts(a), attempts(b)) {
+        (Some(a), Some(b)) if a < b => 1.0,
+        (Some(a), Some(b)) if a > b => 0.0,
+        (Some(_), None) => 1.0,
+        (None, Some(_)) => 0.0,
+        _ => 0.5,
+    }
+}
+
+// Names end up in ratings.tsv, achievements.tsv and results.tsv, where a tab would start a new field and a line break a new record. Options::parse checks --player with it too.
+pub fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        Err("A player needs a name.".to_string())
+    } else if name.chars().any(char::is_control) {
+        Err(format!("{name:?} is not a valid player name, it must not contain tabs, line breaks or other control characters."))
+    } else {
+        Ok(())
+    }
+}
+
+// The bot plays by the same options as a person: the same type, range, distribution and tolerance.
+fn take_turn<T: Guessable>(name: &str, options: &Options) -> GameRecord {
+    if name == bot::NAME {
+        println!("\nThe bot's turn.");
+        let (range, secret) = crate::pick_secret::<T>(options);
+        bot::play(Game::new(secret, range, options.tolerance), options.bot_mistakes)
+    } else {
+        println!("\n{name}, it's your turn.");
+        let record = crate::play::<T, _>(options, SystemClock);
+        achievements::record(name, &record.events);
+        results::record(name, &record);
+        record
+    }
+}
+
+pub fn run<T: Guessable>(options: &Options, players: &[String]) {
+    let [a, b] = players else {
+        eprintln!("A match needs exactly two players, e.g. `match alice bob` or `match alice bot`.");
+        std::process::exit(2);
+    };
+    if let Err(message) = check_name(a).and_then(|()| check_name(b)) {
+        eprintln!("{message}");
+        std::process::exit(2);
+    }
+    if a == b {
+        eprintln!("A player cannot play a match against themselves.");
+        std::process::exit(2);
+    }
+
+    let mut ratings = match Ratings::load() {
+        Ok(ratings) => ratings,
+        Err(error) => {
+            eprintln!("Could not read the ratings: {error}");
+            std::process::exit(1);
+        }
+    };
+    let before = (ratings.rating(a), ratings.rating(b));
+
+    let rounds = options.rounds;
+    println!("{a} vs {b}, best of {rounds}.");
+
+    let (mut points_a, mut points_b) = (0.0, 0.0);
+    for round in 1..=rounds {
+        println!("\nRound {round} of {rounds}.");
+        let record_a = take_turn::<T>(a, options);
+        let record_b = take_turn::<T>(b, options);
+
+        let score = round_score(&record_a, &record_b);
+        ratings.record_round(a, b, score);
+        points_a += score;
+        points_b += 1.0 - score;
+
+        let winner = match score {
+            1.0 => format!("{a} wins the round"),
+            0.0 => format!("{b} wins the round"),
+            _ => "The round is a draw".to_string(),
+        };
+        println!("\n{winner}. Score: {a} {points_a} - {points_b} {b}");
+
+        let remaining = (rounds - round) as f64;
+        if (points_a - points_b).abs() > remaining {
+            break;
+        }
+    }
+
+    println!();
+    if points_a > points_b {
+        println!("{a} wins the match!");
+    } else if points_b > points_a {
+        println!("{b} wins the match!");
+    } else {
+        println!("The match is a draw.");
+    }
+
+    for (name, old) in [(a, before.0), (b, before.1)] {
+        let new = ratings.rating(name);
+        println!("{name}: {old:.0} -> {new:.0} ({:+.0})", new - old);
+    }
+
+    if let Err(error) = ratings.save() {
+        eprintln!("Could not save the ratings: {error}");
+    }
+}
+
+pub fn show_ratings() {
+    match Ratings::load() {
+        Ok(ratings) => println!("{ratings}"),
+        Err(error) => eprintln!("Could not read the ratings: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_names_that_break_the_ratings_file() {
+        assert_eq!(check_name("alice"), Ok(()));
+        assert_eq!(check_name("Zoë Smith"), Ok(()));
+        assert!(check_name("").is_err());
+        assert_eq!(
+            check_name("al\tice"),
+            Err("\"al\\tice\" is not a valid player name, it must not contain tabs, line breaks or other control characters.".to_string())
+        );
+        assert!(check_name("bob\n").is_err());
+    }
+}
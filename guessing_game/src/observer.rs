@@ -0,0 +1,269 @@
+// Observers let other code follow a game without touching the game loop. The loop calls every registered observer at each step, and each observer decides for itself what to do with it.
+// Trait objects make this possible: Box<dyn GameObserver> can hold any type that implements the trait, so one Vec can hold a logger next to a bell.
+
+use std::cmp::Ordering;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::record::Outcome;
+use crate::storage;
+
+const LOG_FILE: &str = "game.log";
+
+// When something happened: the wall clock time, the time since the game started and how many guesses had been counted by then.
+#[derive(Debug, Clone, Copy)]
+pub struct Stamp {
+    pub at: SystemTime,
+    pub elapsed: Duration,
+    pub attempt: u32,
+}
+
+impl Stamp {
+    pub fn now(elapsed: Duration, attempt: u32) -> Stamp {
+        Stamp { at: SystemTime::now(), elapsed, attempt }
+    }
+}
+
+// Every method has an empty default body, so an observer only implements the callbacks it cares about.
+pub trait GameObserver {
+    fn game_started(&mut self, _range: &str, _stamp: Stamp) {}
+    fn guess_made(&mut self, _guess: &str, _ordering: Ordering, _stamp: Stamp) {}
+    fn invalid_input(&mut self, _input: &str, _stamp: Stamp) {}
+    fn game_won(&mut self, _stamp: Stamp) {}
+    fn game_lost(&mut self, _outcome: Outcome, _stamp: Stamp) {}
+}
+
+// The observers that can be turned on with --observe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Builtin {
+    Log,
+    Stats,
+    Bell,
+}
+
+pub const BUILTINS: [(&str, Builtin); 3] = [("log", Builtin::Log), ("stats", Builtin::Stats), ("bell", Builtin::Bell)];
+
+impl Builtin {
+    pub fn parse(name: &str) -> Option<Builtin> {
+        BUILTINS.iter().find(|(n, _)| *n == name).map(|&(_, builtin)| builtin)
+    }
+
+    pub fn create(self) -> Box<dyn GameObserver> {
+        match self {
+            Builtin::Log => Box::new(Logger::default()),
+            Builtin::Stats => Box::new(Stats::default()),
+            Builtin::Bell => Box::new(Bell),
+        }
+    }
+}
+
+// Hands every callback on to each registered observer, in the order they were registered.
+#[derive(Default)]
+pub struct Observers {
+    list: Vec<Box<dyn GameObserver>>,
+}
+
+impl Observers {
+    pub fn register(&mut self, observer: Box<dyn GameObserver>) {
+        self.list.push(observer);
+    }
+}
+
+impl GameObserver for Observers {
+    fn game_started(&mut self, range: &str, stamp: Stamp) {
+        for observer in &mut self.list {
+            observer.game_started(range, stamp);
+        }
+    }
+
+    fn guess_made(&mut self, guess: &str, ordering: Ordering, stamp: Stamp) {
+        for observer in &mut self.list {
+            observer.guess_made(guess, ordering, stamp);
+        }
+    }
+
+    fn invalid_input(&mut self, input: &str, stamp: Stamp) {
+        for observer in &mut self.list {
+            observer.invalid_input(input, stamp);
+        }
+    }
+
+    fn game_won(&mut self, stamp: Stamp) {
+        for observer in &mut self.list {
+            observer.game_won(stamp);
+        }
+    }
+
+    fn game_lost(&mut self, outcome: Outcome, stamp: Stamp) {
+        for observer in &mut self.list {
+            observer.game_lost(outcome, stamp);
+        }
+    }
+}
+
+// Appends one line per callback to game.log: the Unix time, the time into the game, the attempt and what happened. The lines are collected during the game and written at its end, so a slow disk never holds up a guess.
+#[derive(Default)]
+pub struct Logger {
+    lines: Vec<String>,
+}
+
+impl Logger {
+    fn log(&mut self, stamp: Stamp, what: String) {
+        let unix = stamp.at.duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.lines.push(format!("{:.3}\t+{:.1}s\t#{}\t{what}", unix.as_secs_f64(), stamp.elapsed.as_secs_f64(), stamp.attempt));
+    }
+
+    fn flush(&mut self) {
+        let lines: String = self.lines.drain(..).map(|line| line + "\n").collect();
+        if let Err(error) = storage::append(LOG_FILE, &lines) {
+            eprintln!("Could not write {LOG_FILE}: {error}");
+        }
+    }
+}
+
+impl GameObserver for Logger {
+    fn game_started(&mut self, range: &str, stamp: Stamp) {
+        self.log(stamp, format!("started {range}"));
+    }
+
+    fn guess_made(&mut self, guess: &str, ordering: Ordering, stamp: Stamp) {
+        self.log(stamp, format!("guessed {guess} {ordering:?}"));
+    }
+
+    fn invalid_input(&mut self, input: &str, stamp: Stamp) {
+        self.log(stamp, format!("invalid {input:?}"));
+    }
+
+    fn game_won(&mut self, stamp: Stamp) {
+        self.log(stamp, "won".to_string());
+        self.flush();
+    }
+
+    fn game_lost(&mut self, outcome: Outcome, stamp: Stamp) {
+        self.log(stamp, format!("lost {outcome:?}"));
+        self.flush();
+    }
+}
+
+// Counts what happened and prints the numbers when the game ends.
+#[derive(Default, Debug, PartialEq)]
+pub struct Stats {
+    pub too_small: u32,
+    pub too_big: u32,
+    pub invalid: u32,
+    pub slowest: Duration,
+    last: Duration,
+}
+
+impl Stats {
+    fn report(&self, stamp: Stamp) {
+        let per_guess = if stamp.attempt == 0 { 0.0 } else { stamp.elapsed.as_secs_f64() / stamp.attempt as f64 };
+        println!(
+            "Stats: {} guesses ({} too small, {} too big), {} invalid inputs, {per_guess:.1}s per guess, slowest {:.1}s.",
+            stamp.attempt,
+            self.too_small,
+            self.too_big,
+            self.invalid,
+            self.slowest.as_secs_f64()
+        );
+    }
+}
+
+impl GameObserver for Stats {
+    fn guess_made(&mut self, _guess: &str, ordering: Ordering, stamp: Stamp) {
+        match ordering {
+            Ordering::Less => self.too_small += 1,
+            Ordering::Greater => self.too_big += 1,
+            Ordering::Equal => {}
+        }
+        self.slowest = self.slowest.max(stamp.elapsed - self.last);
+        self.last = stamp.elapsed;
+    }
+
+    fn invalid_input(&mut self, _input: &str, _stamp: Stamp) {
+        self.invalid += 1;
+    }
+
+    fn game_won(&mut self, stamp: Stamp) {
+        self.report(stamp);
+    }
+
+    fn game_lost(&mut self, _outcome: Outcome, stamp: Stamp) {
+        self.report(stamp);
+    }
+}
+
+// Rings the terminal bell: once for a win, twice for a typo. \x07 is the ASCII BEL character, which terminals turn into a beep or a flash.
+pub struct Bell;
+
+impl GameObserver for Bell {
+    fn invalid_input(&mut self, _input: &str, _stamp: Stamp) {
+        print!("\x07\x07");
+    }
+
+    fn game_won(&mut self, _stamp: Stamp) {
+        print!("\x07");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // Rc<RefCell<..>> lets the test keep a handle on what the observer saw after handing the observer itself over to Observers.
+    struct Recorder(Rc<RefCell<Vec<String>>>);
+
+    impl GameObserver for Recorder {
+        fn game_started(&mut self, range: &str, stamp: Stamp) {
+            self.0.borrow_mut().push(format!("started {range} #{}", stamp.attempt));
+        }
+
+        fn guess_made(&mut self, guess: &str, ordering: Ordering, stamp: Stamp) {
+            self.0.borrow_mut().push(format!("{guess} {ordering:?} #{}", stamp.attempt));
+        }
+
+        fn game_won(&mut self, stamp: Stamp) {
+            self.0.borrow_mut().push(format!("won #{}", stamp.attempt));
+        }
+    }
+
+    fn stamp(seconds: u64, attempt: u32) -> Stamp {
+        Stamp::now(Duration::from_secs(seconds), attempt)
+    }
+
+    #[test]
+    fn every_registered_observer_hears_every_callback() {
+        let (first, second) = (Rc::new(RefCell::new(Vec::new())), Rc::new(RefCell::new(Vec::new())));
+        let mut observers = Observers::default();
+        observers.register(Box::new(Recorder(Rc::clone(&first))));
+        observers.register(Box::new(Recorder(Rc::clone(&second))));
+
+        observers.game_started("1..=100", stamp(0, 0));
+        observers.invalid_input("abc", stamp(1, 0));
+        observers.guess_made("50", Ordering::Less, stamp(2, 1));
+        observers.game_won(stamp(3, 1));
+
+        let expected = ["started 1..=100 #0", "50 Less #1", "won #1"];
+        assert_eq!(*first.borrow(), expected);
+        assert_eq!(*second.borrow(), expected);
+    }
+
+    #[test]
+    fn stats_count_the_answers() {
+        let mut stats = Stats::default();
+        stats.guess_made("50", Ordering::Less, stamp(2, 1));
+        stats.invalid_input("x", stamp(3, 1));
+        stats.guess_made("75", Ordering::Greater, stamp(9, 2));
+        stats.guess_made("60", Ordering::Equal, stamp(10, 3));
+
+        assert_eq!((stats.too_small, stats.too_big, stats.invalid), (1, 1, 1));
+        assert_eq!(stats.slowest, Duration::from_secs(7));
+    }
+
+    #[test]
+    fn parses_the_builtin_names() {
+        assert_eq!(Builtin::parse("stats"), Some(Builtin::Stats));
+        assert_eq!(Builtin::parse("sound"), None);
+    }
+}
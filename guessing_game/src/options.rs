@@ -0,0 +1,255 @@
+// A small hand written parser for the command line. Crates like clap do this for bigger programs, but walking over std::env::args is enough here.
+
+use std::env;
+use std::process;
+use std::time::Duration;
+
+use crate::distribution::{self, Distribution};
+use crate::match_play;
+use crate::observer::{self, Builtin};
+
+pub enum Command {
+    Play,
+    Bulls,
+    BullsSolve,
+    Match(Vec<String>),
+    Ratings,
+    CheckDist,
+    Race,
+    Achievements,
+    Stats,
+    Serve,
+}
+
+pub enum Kind {
+    U32,
+    I64,
+    F64,
+    Char,
+}
+
+pub struct Options {
+    pub command: Command,
+    pub kind: Kind,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub tolerance: f64,
+    pub time_limit: Option<Duration>,
+    pub rounds: u32,
+    pub free_redundant: bool,
+    pub analyze: bool,
+    pub explain: bool,
+    pub tui: bool,
+    pub observers: Vec<Builtin>,
+    pub distribution: Option<Distribution>,
+    pub samples: u64,
+    pub bot_mistakes: f64,
+    pub shared_feedback: bool,
+    pub player: String,
+    pub port: u16,
+}
+
+const USAGE: &str = "usage: guessing_game [bulls | bulls-solve | match PLAYER PLAYER | ratings | check-dist | race | achievements | stats | serve] [--port N] [--player NAME] [--rounds N] [--bot-mistakes PERCENT] [--shared-feedback] [--distribution uniform|triangular|round|gaussian] [--samples N] [--type u32|i64|f64|char] [--min VALUE] [--max VALUE] [--tolerance FLOAT] [--time-attack SECONDS] [--free-redundant] [--analyze] [--explain] [--tui] [--observe log,stats,bell]";
+
+impl Options {
+    // Exits with a usage message on bad input, the same way most command line tools do.
+    pub fn from_args() -> Options {
+        match Options::parse(env::args().skip(1)) {
+            Ok(options) => options,
+            Err(message) => {
+                eprintln!("{message}\n{USAGE}");
+                process::exit(2);
+            }
+        }
+    }
+
+    // impl Trait in argument position accepts any iterator of Strings, so tests or other callers do not need real command line arguments.
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            command: Command::Play,
+            kind: Kind::U32,
+            min: None,
+            max: None,
+            tolerance: 0.5,
+            time_limit: None,
+            rounds: 3,
+            free_redundant: false,
+            analyze: false,
+            explain: false,
+            tui: false,
+            observers: Vec::new(),
+            distribution: None,
+            samples: 100_000,
+            bot_mistakes: 0.0,
+            shared_feedback: false,
+            // Without --player, achievements go to the user that is logged in.
+            player: env::var("USER").unwrap_or_else(|_| "player".to_string()),
+            port: 8080,
+        };
+
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            // A closure that borrows args mutably, so each flag can take the value that follows it.
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+
+            match arg.as_str() {
+                "bulls" => options.command = Command::Bulls,
+                "bulls-solve" => options.command = Command::BullsSolve,
+                "match" => options.command = Command::Match(Vec::new()),
+                "ratings" => options.command = Command::Ratings,
+                "check-dist" => options.command = Command::CheckDist,
+                "race" => options.command = Command::Race,
+                "achievements" => options.command = Command::Achievements,
+                "stats" => options.command = Command::Stats,
+                "serve" => options.command = Command::Serve,
+                "--player" => {
+                    let name = value()?;
+                    match_play::check_name(&name)?;
+                    options.player = name;
+                }
+                "--type" => {
+                    options.kind = match value()?.as_str() {
+                        "u32" => Kind::U32,
+                        "i64" => Kind::I64,
+                        "f64" => Kind::F64,
+                        "char" => Kind::Char,
+                        other => return Err(format!("unknown type `{other}`")),
+                    }
+                }
+                "--free-redundant" => options.free_redundant = true,
+                "--analyze" => options.analyze = true,
+                "--explain" => options.explain = true,
+                "--tui" => options.tui = true,
+                "--observe" => {
+                    for name in value()?.split(',') {
+                        match Builtin::parse(name) {
+                            Some(builtin) if !options.observers.contains(&builtin) => options.observers.push(builtin),
+                            Some(_) => {}
+                            None => {
+                                let names: Vec<&str> = observer::BUILTINS.iter().map(|(n, _)| *n).collect();
+                                return Err(format!("unknown observer `{name}`, expected one of {}", names.join(", ")));
+                            }
+                        }
+                    }
+                }
+                "--min" => options.min = Some(value()?),
+                "--max" => options.max = Some(value()?),
+                "--tolerance" => {
+                    let tolerance = value()?;
+                    options.tolerance = match tolerance.parse() {
+                        Ok(t) if t >= 0.0 => t,
+                        _ => return Err(format!("`{tolerance}` is not a valid tolerance")),
+                    }
+                }
+                "--time-attack" => {
+                    let seconds = value()?;
+                    options.time_limit = match seconds.parse() {
+                        Ok(s) if s > 0 => Some(Duration::from_secs(s)),
+                        _ => return Err(format!("`{seconds}` is not a number of seconds")),
+                    }
+                }
+                "--distribution" => {
+                    let name = value()?;
+                    options.distribution = match Distribution::parse(&name) {
+                        Some(d) => Some(d),
+                        None => {
+                            let names: Vec<String> = distribution::ALL.iter().map(|d| d.to_string()).collect();
+                            return Err(format!("unknown distribution `{name}`, expected one of {}", names.join(", ")));
+                        }
+                    }
+                }
+                "--samples" => {
+                    let samples = value()?;
+                    options.samples = match samples.parse() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(format!("`{samples}` is not a number of samples")),
+                    }
+                }
+                "--shared-feedback" => options.shared_feedback = true,
+                "--bot-mistakes" => {
+                    let percent = value()?;
+                    options.bot_mistakes = match percent.trim_end_matches('%').parse::<f64>() {
+                        Ok(p) if (0.0..=100.0).contains(&p) => p / 100.0,
+                        _ => return Err(format!("`{percent}` is not a percentage from 0 to 100")),
+                    }
+                }
+                "--port" => {
+                    let port = value()?;
+                    options.port = port.parse().map_err(|_| format!("`{port}` is not a port number"))?;
+                }
+                "--rounds" => {
+                    let rounds = value()?;
+                    options.rounds = match rounds.parse() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(format!("`{rounds}` is not a number of rounds")),
+                    }
+                }
+                // Any other word after `match` is the name of a player.
+                name if !name.starts_with("--") && matches!(options.command, Command::Match(_)) => {
+                    if let Command::Match(players) = &mut options.command {
+                        players.push(name.to_string());
+                    }
+                }
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn picks_the_type() {
+        assert!(matches!(parse("").unwrap().kind, Kind::U32));
+        assert!(matches!(parse("--type i64").unwrap().kind, Kind::I64));
+        assert!(matches!(parse("race --type char").unwrap().kind, Kind::Char));
+        assert_eq!(parse("--type u8").err(), Some("unknown type `u8`".to_string()));
+        assert_eq!(parse("--type").err(), Some("--type needs a value".to_string()));
+    }
+
+    #[test]
+    fn reads_the_range_as_the_chosen_type() {
+        let options = parse("--type i64 --min -100 --max -1").unwrap();
+        assert_eq!(crate::range_from::<i64>(&options), Ok(-100..=-1));
+        assert_eq!(crate::range_from::<char>(&parse("--min x").unwrap()), Ok('x'..='z'));
+        assert_eq!(crate::range_from::<f64>(&parse("--max 2.5").unwrap()), Ok(0.0..=2.5));
+
+        assert_eq!(crate::range_from::<u32>(&parse("--min -1").unwrap()), Err("`-1` is not a valid number".to_string()));
+        assert_eq!(crate::range_from::<u32>(&parse("--min 10 --max 5").unwrap()), Err("the range 10 to 5 is empty".to_string()));
+        assert_eq!(crate::range_from::<f64>(&parse("--min NaN").unwrap()), Err("the range NaN to 100 is empty".to_string()));
+        assert_eq!(
+            crate::range_from::<f64>(&parse("--max inf").unwrap()),
+            Err("the range 0 to inf is too wide, the bounds and the distance between them must be finite".to_string())
+        );
+        assert!(crate::range_from::<f64>(&parse("--min -inf --max 0").unwrap()).is_err());
+        assert!(crate::range_from::<f64>(&parse("--min -1e308 --max 1e308").unwrap()).is_err());
+        assert!(crate::range_from::<f64>(&parse("--min -1e307 --max 1e307").unwrap()).is_ok());
+        assert!(crate::range_from::<i64>(&parse("--min -9223372036854775808 --max 9223372036854775807").unwrap()).is_ok());
+        assert_eq!(parse("--max").err(), Some("--max needs a value".to_string()));
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert_eq!(parse("--tolerance -1").err(), Some("`-1` is not a valid tolerance".to_string()));
+        assert_eq!(parse("--time-attack 0").err(), Some("`0` is not a number of seconds".to_string()));
+        assert_eq!(parse("--bot-mistakes 150%").err(), Some("`150%` is not a percentage from 0 to 100".to_string()));
+        assert_eq!(parse("--colour").err(), Some("unexpected argument `--colour`".to_string()));
+    }
+
+    #[test]
+    fn rejects_player_names_that_break_the_files() {
+        let player = |name: &str| Options::parse(["--player", name].map(str::to_string).into_iter()).map(|options| options.player);
+        assert_eq!(player("alice"), Ok("alice".to_string()));
+        assert!(player("").is_err());
+        assert!(player("al\tice").is_err());
+        assert!(player("bob\n").is_err());
+    }
+}
@@ -0,0 +1,140 @@
+// The player and the bot take turns guessing the same secret, and whoever finds it first wins. Normally each side only hears the answers to its own guesses. With shared feedback both hear everything, so every answer helps both of them.
+
+use std::cmp::Ordering;
+use std::io;
+
+use rand::Rng;
+
+use crate::bot::Bot;
+use crate::distribution::Distribution;
+use crate::game::{Game, Guessable};
+use crate::options::Options;
+
+fn feedback(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "Too small!",
+        Ordering::Greater => "Too big!",
+        Ordering::Equal => "Correct!",
+    }
+}
+
+// Asks until the player enters a guess inside the range. None means the input has ended.
+fn read_guess<T: Guessable>(game: &Game<T>) -> Option<T> {
+    loop {
+        println!("Your guess ({}):", game.feasible());
+
+        let mut line = String::new();
+        io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read line");
+        if line.is_empty() {
+            return None;
+        }
+
+        let input = line.trim();
+        match T::parse_guess(input) {
+            Ok(guess) if game.range().contains(&guess) => return Some(guess),
+            Ok(_) => println!("Please guess between {} and {}.", game.range().start(), game.range().end()),
+            Err(error) => println!("Invalid guess: {}", error.render(input)),
+        }
+    }
+}
+
+// The bot's turn: it guesses on its own Game and learns from the answer.
+fn bot_turn<T: Guessable, R: Rng>(bot: &mut Bot<T>, opponent: &mut Game<T>, rng: &mut R) -> (T, Ordering) {
+    let guess = bot.next_guess(rng);
+    let ordering = opponent.guess(guess).expect("the bot only guesses inside the range");
+    bot.learn(guess, ordering);
+    (guess, ordering)
+}
+
+// With shared feedback the bot hears the answers to the player's guesses. Its own Game hears them too, so the bot's interval and the one its Game reports stay the same.
+fn share_with_bot<T: Guessable>(bot: &mut Bot<T>, opponent: &mut Game<T>, guess: T, ordering: Ordering) {
+    bot.learn(guess, ordering);
+    opponent.observe(guess, ordering);
+}
+
+pub fn run<T: Guessable>(options: &Options) {
+    let range = crate::range_from::<T>(options).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        std::process::exit(2);
+    });
+
+    let distribution = options.distribution.unwrap_or(Distribution::Uniform);
+    let secret = distribution.sample(&range, &mut rand::thread_rng());
+
+    // Both sides play their own Game on the same secret, so each keeps its own attempts and feasible interval.
+    let mut player = Game::new(secret, range.clone(), options.tolerance);
+    let mut opponent = Game::new(secret, range.clone(), options.tolerance);
+    let mut bot = Bot::new(&range, options.bot_mistakes);
+
+    println!("Race the bot to the {} between {} and {}! You go first.", T::NAME, range.start(), range.end());
+    if options.shared_feedback {
+        println!("Feedback is shared: you both hear every answer.");
+    }
+
+    loop {
+        let Some(guess) = read_guess(&player) else {
+            println!("You gave up. The {} was {secret}.", T::NAME);
+            return;
+        };
+        let ordering = player.guess(guess).expect("read_guess only returns guesses inside the range");
+        println!("You guessed {guess}: {}", feedback(ordering));
+        if ordering == Ordering::Equal {
+            println!("You win the race after {} guesses!", player.attempts());
+            return;
+        }
+        if options.shared_feedback {
+            share_with_bot(&mut bot, &mut opponent, guess, ordering);
+        }
+
+        let (guess, ordering) = bot_turn(&mut bot, &mut opponent, &mut rand::thread_rng());
+        if ordering == Ordering::Equal {
+            println!("The bot guessed {guess} and wins the race after {} guesses.", opponent.attempts());
+            return;
+        }
+        if options.shared_feedback {
+            println!("The bot guessed {guess}: {}", feedback(ordering));
+            player.observe(guess, ordering);
+        } else {
+            println!("The bot made its guess.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn shared_feedback_narrows_both_sides() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut player = Game::new(30u32, 1..=100, 0.0);
+        let mut opponent = Game::new(30u32, 1..=100, 0.0);
+        let mut bot = Bot::new(&(1..=100), 0.0);
+
+        let ordering = player.guess(50).unwrap();
+        share_with_bot(&mut bot, &mut opponent, 50, ordering);
+        assert_eq!(opponent.feasible().to_string(), "between 1 and 49");
+
+        // The bot starts from what the player learned: the midpoint of 1 to 49, not of 1 to 100.
+        let (guess, ordering) = bot_turn(&mut bot, &mut opponent, &mut rng);
+        assert_eq!((guess, ordering), (25, Ordering::Less));
+        player.observe(guess, ordering);
+        assert_eq!(player.feasible().to_string(), "between 26 and 49");
+        assert_eq!(opponent.feasible().to_string(), "between 26 and 49");
+        assert_eq!((player.attempts(), opponent.attempts()), (1, 1));
+    }
+
+    #[test]
+    fn without_sharing_the_bot_only_hears_itself() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut opponent = Game::new(30u32, 1..=100, 0.0);
+        let mut bot = Bot::new(&(1..=100), 0.0);
+        let (guess, ordering) = bot_turn(&mut bot, &mut opponent, &mut rng);
+        assert_eq!((guess, ordering), (51, Ordering::Greater));
+        assert_eq!(bot_turn(&mut bot, &mut opponent, &mut rng).0, 26);
+    }
+}
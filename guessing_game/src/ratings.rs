@@ -0,0 +1,143 @@
+// The ladder of every player who has played a match, kept in ratings.tsv. Each line holds a name, a rating and the number of rounds won, drawn and lost, separated by tabs.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use crate::elo;
+use crate::storage;
+
+const FILE: &str = "ratings.tsv";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    pub name: String,
+    pub rating: f64,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Player {
+    fn new(name: &str) -> Player {
+        Player { name: name.to_string(), rating: elo::INITIAL_RATING, wins: 0, draws: 0, losses: 0 }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    fn parse(line: &str) -> Option<Player> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            [name, rating, wins, draws, losses] => Some(Player {
+                name: name.to_string(),
+                rating: rating.parse().ok()?,
+                wins: wins.parse().ok()?,
+                draws: draws.parse().ok()?,
+                losses: losses.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub struct Ratings {
+    players: HashMap<String, Player>,
+}
+
+impl Ratings {
+    pub fn load() -> io::Result<Ratings> {
+        let mut players = HashMap::new();
+        for line in storage::read(FILE)?.lines() {
+            match Player::parse(line) {
+                Some(player) => {
+                    players.insert(player.name.clone(), player);
+                }
+                None => eprintln!("Skipping a broken line in {FILE}: {line}"),
+            }
+        }
+        Ok(Ratings { players })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut contents = String::new();
+        for p in self.ladder() {
+            contents += &format!("{}\t{}\t{}\t{}\t{}\n", p.name, p.rating, p.wins, p.draws, p.losses);
+        }
+        storage::write(FILE, &contents)
+    }
+
+    // entry().or_insert_with() returns the existing player, or inserts a new one first.
+    pub fn player(&mut self, name: &str) -> &mut Player {
+        self.players.entry(name.to_string()).or_insert_with(|| Player::new(name))
+    }
+
+    pub fn rating(&mut self, name: &str) -> f64 {
+        self.player(name).rating
+    }
+
+    // score_a is 1 when a won the round, 0.5 for a draw and 0 when b won.
+    pub fn record_round(&mut self, a: &str, b: &str, score_a: f64) {
+        let (rating_a, rating_b) = elo::rate_game(self.rating(a), self.rating(b), score_a);
+        for (name, rating, score) in [(a, rating_a, score_a), (b, rating_b, 1.0 - score_a)] {
+            let player = self.player(name);
+            player.rating = rating;
+            match score {
+                1.0 => player.wins += 1,
+                0.0 => player.losses += 1,
+                _ => player.draws += 1,
+            }
+        }
+    }
+
+    // Best rating first. f64 is only PartialOrd, so total_cmp is used to get a full ordering.
+    pub fn ladder(&self) -> Vec<&Player> {
+        let mut players: Vec<&Player> = self.players.values().collect();
+        players.sort_by(|a, b| b.rating.total_cmp(&a.rating).then_with(|| a.name.cmp(&b.name)));
+        players
+    }
+}
+
+impl fmt::Display for Ratings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.players.is_empty() {
+            return write!(f, "No matches have been played yet.");
+        }
+        write!(f, "  #  {:<16} {:>6} {:>6} {:>4} {:>4} {:>4}", "Player", "Rating", "Rounds", "W", "D", "L")?;
+        for (rank, p) in self.ladder().iter().enumerate() {
+            write!(
+                f,
+                "\n{:>3}. {:<16} {:>6.0} {:>6} {:>4} {:>4} {:>4}",
+                rank + 1, p.name, p.rating, p.games(), p.wins, p.draws, p.losses
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_rounds_and_sorts_the_ladder() {
+        let mut ratings = Ratings { players: HashMap::new() };
+        ratings.record_round("alice", "bob", 1.0);
+        ratings.record_round("carol", "bob", 0.5);
+
+        let ladder = ratings.ladder();
+        let names: Vec<&str> = ladder.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alice", "carol", "bob"]);
+        assert_eq!(ladder[0].rating, 1516.0);
+        assert_eq!((ladder[2].wins, ladder[2].draws, ladder[2].losses), (0, 1, 1));
+    }
+
+    #[test]
+    fn parses_saved_lines() {
+        let player = Player::parse("alice\t1516.5\t3\t1\t2").unwrap();
+        assert_eq!(player.rating, 1516.5);
+        assert_eq!(player.games(), 6);
+        assert_eq!(Player::parse("alice\t1500"), None);
+    }
+}
@@ -0,0 +1,72 @@
+// What is left of a game once it is over. The guesses are kept as text, so a record looks the same whatever type the game was played with, and it is what gets stored when results are saved.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::Duration;
+
+use crate::game::Redundant;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Won,
+    OutOfTime,
+    GaveUp,
+}
+
+#[derive(Debug, Clone)]
+pub struct GuessRecord {
+    pub guess: String,
+    pub ordering: Ordering,
+    pub time: Duration,
+}
+
+// Everything that happened during a game, in order. Achievements are worked out from this stream instead of from the game itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Started { range: String },
+    Invalid { input: String },
+    Redundant { guess: String, reason: Redundant },
+    // optimal is true when no other guess would have been worth more bits on average, see analysis::expected_bits.
+    Guessed { guess: String, ordering: Ordering, optimal: bool },
+    Finished { outcome: Outcome },
+}
+
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    pub outcome: Outcome,
+    pub guesses: Vec<GuessRecord>,
+    pub total: Duration,
+    pub time_limit: Option<Duration>,
+    pub events: Vec<Event>,
+}
+
+impl GameRecord {
+    pub fn attempts(&self) -> usize {
+        self.guesses.len()
+    }
+}
+
+// Implementing Display lets the summary be printed with {} like any other value.
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let outcome = match self.outcome {
+            Outcome::Won => "won",
+            Outcome::OutOfTime => "ran out of time",
+            Outcome::GaveUp => "gave up",
+        };
+        write!(f, "Summary: {outcome} after {} guesses in {:.1}s", self.attempts(), self.total.as_secs_f64())?;
+        if let Some(limit) = self.time_limit {
+            write!(f, " (time limit {}s)", limit.as_secs())?;
+        }
+
+        for (i, guess) in self.guesses.iter().enumerate() {
+            let feedback = match guess.ordering {
+                Ordering::Less => "too small",
+                Ordering::Greater => "too big",
+                Ordering::Equal => "correct",
+            };
+            write!(f, "\n  {:>2}. {:<12} {:<10} {:>6.1}s", i + 1, guess.guess, feedback, guess.time.as_secs_f64())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,307 @@
+// Every finished game adds one line to results.tsv: when it ended as Unix seconds, the player, the range, the outcome, the number of guesses and how long the game took in milliseconds. The file is only ever appended to. The stats command reads it back and sums it up.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::record::{Event, GameRecord, Outcome};
+use crate::storage;
+
+const FILE: &str = "results.tsv";
+const DAY: u64 = 24 * 60 * 60;
+const BAR_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    pub ended: u64,
+    pub player: String,
+    pub range: String,
+    pub outcome: Outcome,
+    pub attempts: usize,
+    pub duration: Duration,
+}
+
+impl GameResult {
+    fn line(&self) -> String {
+        let outcome = match self.outcome {
+            Outcome::Won => "won",
+            Outcome::OutOfTime => "out-of-time",
+            Outcome::GaveUp => "gave-up",
+        };
+        format!("{}\t{}\t{}\t{outcome}\t{}\t{}\n", self.ended, self.player, self.range, self.attempts, self.duration.as_millis())
+    }
+
+    fn parse(line: &str) -> Option<GameResult> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [ended, player, range, outcome, attempts, millis] = fields.as_slice() else {
+            return None;
+        };
+        let outcome = match *outcome {
+            "won" => Outcome::Won,
+            "out-of-time" => Outcome::OutOfTime,
+            "gave-up" => Outcome::GaveUp,
+            _ => return None,
+        };
+        let attempts = attempts.parse().ok()?;
+        // A game that was given up may have no guesses, but nobody wins without one. Summary::new relies on that when it sorts wins into the histogram.
+        if outcome == Outcome::Won && attempts == 0 {
+            return None;
+        }
+        Some(GameResult {
+            ended: ended.parse().ok()?,
+            player: player.to_string(),
+            range: range.to_string(),
+            outcome,
+            attempts,
+            duration: Duration::from_millis(millis.parse().ok()?),
+        })
+    }
+
+    fn won(&self) -> bool {
+        self.outcome == Outcome::Won
+    }
+}
+
+// Called at the end of every game the player played.
+pub fn record(player: &str, record: &GameRecord) {
+    let range = record.events.iter().find_map(|event| match event {
+        Event::Started { range } => Some(range.as_str()),
+        _ => None,
+    });
+    let result = GameResult {
+        ended: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        player: player.to_string(),
+        range: range.unwrap_or("?").to_string(),
+        outcome: record.outcome,
+        attempts: record.attempts(),
+        duration: record.total,
+    };
+    if let Err(error) = storage::append(FILE, &result.line()) {
+        eprintln!("Could not save the result: {error}");
+    }
+}
+
+fn load() -> io::Result<Vec<GameResult>> {
+    let mut results = Vec::new();
+    for line in storage::read(FILE)?.lines() {
+        match GameResult::parse(line) {
+            Some(result) => results.push(result),
+            None => eprintln!("Skipping a broken line in {FILE}: {line}"),
+        }
+    }
+    Ok(results)
+}
+
+// Turns days since 1970-01-01 into a year, month and day. This is Howard Hinnant's days_from_civil algorithm run backwards: it counts in 400 year eras that start on the 1st of March, so the leap day is always the last day of a year.
+pub fn civil_date(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// The Monday that starts the week, in days since 1970-01-01. That day was a Thursday, three days after a Monday.
+fn week_start(ended: u64) -> i64 {
+    let days = (ended / DAY) as i64;
+    days - (days + 3).rem_euclid(7)
+}
+
+fn median(sorted: &[usize]) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => sorted[n / 2] as f64,
+        n => (sorted[n / 2 - 1] + sorted[n / 2]) as f64 / 2.0,
+    }
+}
+
+pub struct Week {
+    pub start: i64,
+    pub games: usize,
+    pub wins: usize,
+    pub average_attempts: f64,
+}
+
+// Everything the stats command shows. Attempts only count games that were won, since a game that was given up says little about how many guesses it needed.
+pub struct Summary {
+    pub games: usize,
+    pub wins: usize,
+    pub average_attempts: f64,
+    pub median_attempts: f64,
+    pub average_duration: Duration,
+    // How many won games needed 1, 2, 3, ... guesses.
+    pub histogram: Vec<usize>,
+    pub current_streak: usize,
+    pub best_streak: usize,
+    pub weeks: Vec<Week>,
+}
+
+impl Summary {
+    // The results must be in the order they were played, which is the order of the file.
+    pub fn new(results: &[GameResult]) -> Summary {
+        let mut attempts: Vec<usize> = results.iter().filter(|r| r.won()).map(|r| r.attempts).collect();
+        attempts.sort();
+        let wins = attempts.len();
+
+        let mut histogram = vec![0; attempts.last().copied().unwrap_or(0)];
+        for &a in &attempts {
+            histogram[a - 1] += 1;
+        }
+
+        let (mut current_streak, mut best_streak) = (0, 0);
+        for result in results {
+            current_streak = if result.won() { current_streak + 1 } else { 0 };
+            best_streak = best_streak.max(current_streak);
+        }
+
+        // A BTreeMap keeps its keys sorted, so the weeks come out in calendar order.
+        let mut weeks: BTreeMap<i64, Week> = BTreeMap::new();
+        for result in results {
+            let start = week_start(result.ended);
+            let week = weeks.entry(start).or_insert(Week { start, games: 0, wins: 0, average_attempts: 0.0 });
+            week.games += 1;
+            if result.won() {
+                // A running mean, so the week does not need to keep every result.
+                week.wins += 1;
+                week.average_attempts += (result.attempts as f64 - week.average_attempts) / week.wins as f64;
+            }
+        }
+
+        let total: Duration = results.iter().map(|r| r.duration).sum();
+        Summary {
+            games: results.len(),
+            wins,
+            average_attempts: if wins == 0 { 0.0 } else { attempts.iter().sum::<usize>() as f64 / wins as f64 },
+            median_attempts: median(&attempts),
+            average_duration: if results.is_empty() { Duration::ZERO } else { total / results.len() as u32 },
+            histogram,
+            current_streak,
+            best_streak,
+            weeks: weeks.into_values().collect(),
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 { 0.0 } else { self.wins as f64 / self.games as f64 }
+    }
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 { 0.0 } else { 100.0 * part as f64 / whole as f64 }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Games played:    {}", self.games)?;
+        writeln!(f, "Win rate:        {:.0}% ({} won)", 100.0 * self.win_rate(), self.wins)?;
+        writeln!(f, "Attempts:        {:.1} on average, median {:.1}", self.average_attempts, self.median_attempts)?;
+        writeln!(f, "Time per game:   {:.1}s on average", self.average_duration.as_secs_f64())?;
+        writeln!(f, "Win streak:      {} now, {} at best", self.current_streak, self.best_streak)?;
+
+        // The longest bar is BAR_WIDTH characters, the others are scaled to it.
+        writeln!(f, "\nGuesses needed to win:")?;
+        if self.histogram.is_empty() {
+            writeln!(f, "  no games won yet")?;
+        }
+        let most = self.histogram.iter().copied().max().unwrap_or(0);
+        for (i, &count) in self.histogram.iter().enumerate() {
+            let bar = if most == 0 { 0 } else { (count * BAR_WIDTH).div_ceil(most) };
+            writeln!(f, "  {:>3} | {:<BAR_WIDTH$} {count}", i + 1, "#".repeat(bar))?;
+        }
+
+        write!(f, "\nBy week:")?;
+        for week in &self.weeks {
+            let (year, month, day) = civil_date(week.start);
+            write!(
+                f,
+                "\n  {year}-{month:02}-{day:02}  {:>3} games  {:>3.0}% won  {:.1} attempts",
+                week.games,
+                percent(week.wins, week.games),
+                week.average_attempts
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The stats command, for one player.
+pub fn show(player: &str) {
+    let results = match load() {
+        Ok(results) => results,
+        Err(error) => {
+            eprintln!("Could not read the results: {error}");
+            return;
+        }
+    };
+    let results: Vec<GameResult> = results.into_iter().filter(|r| r.player == player).collect();
+    if results.is_empty() {
+        println!("{player} has not played any games yet.");
+        return;
+    }
+    println!("Statistics of {player}:\n{}", Summary::new(&results));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(ended: u64, outcome: Outcome, attempts: usize) -> GameResult {
+        GameResult { ended, player: "ann".to_string(), range: "1..=100".to_string(), outcome, attempts, duration: Duration::from_millis(1500) }
+    }
+
+    #[test]
+    fn lines_round_trip() {
+        let r = result(1_700_000_000, Outcome::OutOfTime, 4);
+        assert_eq!(GameResult::parse(r.line().trim_end()), Some(r));
+        assert_eq!(GameResult::parse("1700000000\tann\t1..=100\tlost\t4\t1500"), None);
+        assert_eq!(GameResult::parse("1700000000\tann\t1..=100\twon\t0\t1500"), None);
+        assert_eq!(GameResult::parse("1700000000\tann\t1..=100\tgave-up\t0\t1500").map(|r| r.attempts), Some(0));
+    }
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+        assert_eq!(civil_date(20_000), (2024, 10, 4));
+        assert_eq!(civil_date(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        // 2024-10-04 was a Friday, its week started on Monday 2024-09-30.
+        let friday = 20_000 * DAY + 12 * 60 * 60;
+        assert_eq!(civil_date(week_start(friday)), (2024, 9, 30));
+        assert_eq!(civil_date(week_start(0)), (1969, 12, 29));
+    }
+
+    #[test]
+    fn sums_up_the_results() {
+        // From Friday 2024-10-04 to Wednesday 2024-10-09.
+        let (friday, day) = (20_000 * DAY, DAY);
+        let results = [
+            result(friday, Outcome::Won, 3),
+            result(friday + day, Outcome::Won, 7),
+            result(friday + 2 * day, Outcome::GaveUp, 2),
+            result(friday + 4 * day, Outcome::Won, 5),
+            result(friday + 5 * day, Outcome::Won, 5),
+        ];
+        let summary = Summary::new(&results);
+
+        assert_eq!((summary.games, summary.wins), (5, 4));
+        assert_eq!(summary.average_attempts, 5.0);
+        assert_eq!(summary.median_attempts, 5.0);
+        assert_eq!(summary.histogram, [0, 0, 1, 0, 2, 0, 1]);
+        assert_eq!((summary.current_streak, summary.best_streak), (2, 2));
+        assert_eq!(summary.average_duration, Duration::from_millis(1500));
+
+        let weeks: Vec<(usize, usize)> = summary.weeks.iter().map(|w| (w.games, w.wins)).collect();
+        assert_eq!(weeks, [(3, 2), (2, 2)]);
+        assert_eq!(summary.weeks[0].average_attempts, 5.0);
+    }
+}
@@ -0,0 +1,415 @@
+// The game as a JSON API over HTTP, for programs that want to play it. Each game lives in memory under a number, and the answers come from the same Game::guess the terminal game uses.
+//
+//   POST /games                 starts a game, optionally with {"min": 1, "max": 100}
+//   POST /games/{id}/guesses    guesses with {"guess": 50}, or a string like {"guess": "fifty"}
+//   GET  /games/{id}            the game so far
+//
+// Connections are handled one after another on a single thread. That is plenty for a local toy, and it means the games need no locking. Because one slow client would hold up everyone behind it, a connection that has not sent its whole request within TIMEOUT is dropped.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{self, BufReader, Read};
+use std::net::{TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use crate::game::{Game, Guessable};
+use crate::http::{Request, RequestError, Response};
+use crate::json::{self, Value};
+use crate::options::Options;
+
+// How long a client may take to send its request, and to take the response, before the server gives up on it.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// Nobody types a guess this long. Longer strings are turned away before the expression parser sees them, so a request cannot make the server do much work for one guess.
+const MAX_GUESS: usize = 100;
+
+struct Session {
+    game: Game<u32>,
+    guesses: Vec<(u32, Ordering)>,
+}
+
+impl Session {
+    fn won(&self) -> bool {
+        self.guesses.last().is_some_and(|&(_, ordering)| ordering == Ordering::Equal)
+    }
+
+    fn to_json(&self, id: u64) -> String {
+        let guesses: Vec<String> =
+            self.guesses.iter().map(|&(guess, ordering)| format!("{{\"guess\":{guess},\"result\":\"{}\"}}", result(ordering))).collect();
+        // The secret is only given away once it has been found.
+        let secret = if self.won() { format!(",\"secret\":{}", self.game.secret()) } else { String::new() };
+        format!(
+            "{{\"id\":{id},\"min\":{},\"max\":{},\"status\":\"{}\",\"attempts\":{},\"feasible\":{},\"guesses\":[{}]{secret}}}",
+            self.game.range().start(),
+            self.game.range().end(),
+            if self.won() { "won" } else { "playing" },
+            self.game.attempts(),
+            json::string(&self.game.feasible().to_string()),
+            guesses.join(",")
+        )
+    }
+}
+
+fn result(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "too small",
+        Ordering::Greater => "too big",
+        Ordering::Equal => "correct",
+    }
+}
+
+pub struct Api {
+    games: HashMap<u64, Session>,
+    next_id: u64,
+    default_range: RangeInclusive<u32>,
+}
+
+impl Api {
+    pub fn new(default_range: RangeInclusive<u32>) -> Api {
+        Api { games: HashMap::new(), next_id: 1, default_range }
+    }
+
+    // Routing is a match on the method and the path segments. Slice patterns pick the id out of the path.
+    pub fn handle(&mut self, request: &Request) -> Response {
+        let segments = request.segments();
+        let route = match segments.as_slice() {
+            ["games"] => Some(("POST", None)),
+            ["games", id] => Some(("GET", Some(*id))),
+            ["games", id, "guesses"] => Some(("POST", Some(*id))),
+            _ => None,
+        };
+        let Some((allowed, id)) = route else {
+            return Response::error(404, &format!("no route for {}", request.path));
+        };
+        if request.method != allowed {
+            return Response::error(405, &format!("{} only supports {allowed}", request.path));
+        }
+
+        let id = match id.map(str::parse::<u64>) {
+            None => return self.create(&request.body),
+            Some(Ok(id)) if self.games.contains_key(&id) => id,
+            Some(_) => return Response::error(404, &format!("there is no game {}", id.unwrap_or_default())),
+        };
+        match segments.len() {
+            2 => Response::json(200, self.games[&id].to_json(id)),
+            _ => self.guess(id, &request.body),
+        }
+    }
+
+    fn create(&mut self, body: &str) -> Response {
+        let body = match parse_body(body) {
+            Ok(body) => body,
+            Err(response) => return response,
+        };
+        let bound = |name: &str, default: u32| match body.get(name) {
+            None | Some(Value::Null) => Ok(default),
+            Some(Value::Number(n)) => n.parse::<u32>().map_err(|_| format!("`{name}` must be a whole number from 0 to {}", u32::MAX)),
+            Some(_) => Err(format!("`{name}` must be a number")),
+        };
+        let (min, max) = match (bound("min", *self.default_range.start()), bound("max", *self.default_range.end())) {
+            (Ok(min), Ok(max)) if min <= max => (min, max),
+            (Ok(min), Ok(max)) => return Response::error(422, &format!("the range {min} to {max} is empty")),
+            (Err(message), _) | (_, Err(message)) => return Response::error(422, &message),
+        };
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let session = Session { game: Game::random(min..=max, 0.0), guesses: Vec::new() };
+        let response = Response::json(201, session.to_json(id));
+        self.games.insert(id, session);
+        response
+    }
+
+    fn guess(&mut self, id: u64, body: &str) -> Response {
+        let body = match parse_body(body) {
+            Ok(body) => body,
+            Err(response) => return response,
+        };
+        // A string goes through the same parser as the terminal, so "fifty" and "0x32" work here too.
+        let text = match body.get("guess") {
+            Some(Value::Number(text) | Value::String(text)) => text.clone(),
+            _ => return Response::error(400, "the body needs a `guess`, e.g. {\"guess\": 50}"),
+        };
+        if text.chars().count() > MAX_GUESS {
+            return Response::error(422, &format!("invalid guess: longer than {MAX_GUESS} characters"));
+        }
+        let guess = match u32::parse_guess(&text) {
+            Ok(guess) => guess,
+            Err(error) => return Response::error(422, &format!("invalid guess: {error}")),
+        };
+
+        let session = self.games.get_mut(&id).expect("handle checked that the game exists");
+        if session.won() {
+            return Response::error(409, "this game is already won");
+        }
+        let Some(ordering) = session.game.guess(guess) else {
+            let range = session.game.range();
+            return Response::error(422, &format!("guess between {} and {}", range.start(), range.end()));
+        };
+        session.guesses.push((guess, ordering));
+
+        Response::json(
+            200,
+            format!(
+                "{{\"id\":{id},\"guess\":{guess},\"result\":\"{}\",\"status\":\"{}\",\"attempts\":{},\"feasible\":{}}}",
+                result(ordering),
+                if session.won() { "won" } else { "playing" },
+                session.game.attempts(),
+                json::string(&session.game.feasible().to_string())
+            ),
+        )
+    }
+}
+
+// An empty body counts as an empty object, so POST /games works without one.
+fn parse_body(body: &str) -> Result<Value, Response> {
+    if body.trim().is_empty() {
+        return Ok(Value::Object(Default::default()));
+    }
+    match json::parse(body) {
+        Ok(value @ Value::Object(_)) => Ok(value),
+        Ok(_) => Err(Response::error(400, "the body must be a JSON object")),
+        Err(error) => Err(Response::error(400, &format!("invalid JSON: {error}"))),
+    }
+}
+
+// A read timeout on the stream only limits each read on its own, so a client that sends a byte every few seconds would never hit it. Deadline shortens the timeout before every read to whatever is left of the time for the whole request.
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    until: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "the request took too long"));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        let mut stream = self.stream;
+        stream.read(buf)
+    }
+}
+
+fn handle_connection(stream: TcpStream, api: &mut Api, timeout: Duration) {
+    // Without a timeout, read and write block for as long as the client keeps the connection open, and every other client waits with them.
+    if let Err(error) = stream.set_write_timeout(Some(timeout)) {
+        eprintln!("Could not set a timeout: {error}");
+        return;
+    }
+    let mut reader = BufReader::new(Deadline { stream: &stream, until: Instant::now() + timeout });
+    let response = match Request::read(&mut reader) {
+        Ok(Some(request)) => api.handle(&request),
+        Ok(None) => return,
+        Err(RequestError::Io(error)) => {
+            eprintln!("Could not read a request: {error}");
+            return;
+        }
+        Err(error @ RequestError::TooLarge) => Response::error(413, &error.to_string()),
+        Err(error @ RequestError::HeadersTooLarge) => Response::error(431, &error.to_string()),
+        Err(error) => Response::error(400, &error.to_string()),
+    };
+    // &TcpStream implements Write as well as Read, so reading and writing can share the stream.
+    if let Err(error) = response.write_to(&mut &stream) {
+        eprintln!("Could not send a response: {error}");
+    }
+}
+
+pub fn serve(listener: TcpListener, mut api: Api, timeout: Duration) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => handle_connection(stream, &mut api, timeout),
+            Err(error) => eprintln!("Could not accept a connection: {error}"),
+        }
+    }
+}
+
+// The serve command. It only listens on the loopback address, so the API cannot be reached from other machines.
+pub fn run(options: &Options) {
+    let range = crate::range_from::<u32>(options).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        std::process::exit(2);
+    });
+    let listener = match TcpListener::bind(("127.0.0.1", options.port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("Could not listen on port {}: {error}", options.port);
+            std::process::exit(1);
+        }
+    };
+    println!("Serving the guessing game on http://127.0.0.1:{}/games", options.port);
+    serve(listener, Api::new(range), TIMEOUT);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::SocketAddr;
+    use std::thread;
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request { method: method.to_string(), path: path.to_string(), headers: Vec::new(), body: body.to_string() }
+    }
+
+    fn api_with_secret(secret: u32) -> Api {
+        let mut api = Api::new(1..=100);
+        api.games.insert(1, Session { game: Game::new(secret, 1..=100, 0.0), guesses: Vec::new() });
+        api.next_id = 2;
+        api
+    }
+
+    #[test]
+    fn answers_guesses_with_the_game_logic() {
+        let mut api = api_with_secret(42);
+        let response = api.handle(&request("POST", "/games/1/guesses", r#"{"guess": 50}"#));
+        assert_eq!(
+            response,
+            Response::json(200, r#"{"id":1,"guess":50,"result":"too big","status":"playing","attempts":1,"feasible":"between 1 and 49"}"#.to_string())
+        );
+        let response = api.handle(&request("POST", "/games/1/guesses", r#"{"guess": "forty-two"}"#));
+        assert!(response.body.contains(r#""result":"correct","status":"won""#), "{}", response.body);
+
+        let response = api.handle(&request("GET", "/games/1", ""));
+        assert!(response.body.ends_with(r#""guesses":[{"guess":50,"result":"too big"},{"guess":42,"result":"correct"}],"secret":42}"#), "{}", response.body);
+        assert_eq!(api.handle(&request("POST", "/games/1/guesses", r#"{"guess": 1}"#)).status, 409);
+    }
+
+    #[test]
+    fn reports_errors_with_status_codes() {
+        let mut api = api_with_secret(42);
+        let status = |api: &mut Api, method, path, body| api.handle(&request(method, path, body)).status;
+        assert_eq!(status(&mut api, "GET", "/games/9", ""), 404);
+        assert_eq!(status(&mut api, "GET", "/games/x", ""), 404);
+        assert_eq!(status(&mut api, "GET", "/nothing", ""), 404);
+        assert_eq!(status(&mut api, "DELETE", "/games/1", ""), 405);
+        assert_eq!(status(&mut api, "POST", "/games/1/guesses", "{"), 400);
+        assert_eq!(status(&mut api, "POST", "/games/1/guesses", "[50]"), 400);
+        let deep = "[".repeat(100_000);
+        assert_eq!(status(&mut api, "POST", "/games/1/guesses", &deep), 400);
+        let long = format!(r#"{{"guess": "{}1"}}"#, "(".repeat(30_000));
+        assert_eq!(status(&mut api, "POST", "/games/1/guesses", &long), 422);
+        let nested = format!(r#"{{"guess": "{}1"}}"#, "-".repeat(MAX_GUESS - 1));
+        assert_eq!(status(&mut api, "POST", "/games/1/guesses", &nested), 422);
+        assert_eq!(status(&mut api, "POST", "/games/1/guesses", r#"{"guess": "lots"}"#), 422);
+        assert_eq!(status(&mut api, "POST", "/games/1/guesses", r#"{"guess": 500}"#), 422);
+        assert_eq!(status(&mut api, "POST", "/games", r#"{"min": 10, "max": 5}"#), 422);
+        assert_eq!(status(&mut api, "POST", "/games", r#"{"min": -1}"#), 422);
+    }
+
+    #[test]
+    fn creates_games_with_their_own_range() {
+        let mut api = Api::new(1..=100);
+        let response = api.handle(&request("POST", "/games", r#"{"min": 7, "max": 7}"#));
+        assert_eq!(response.status, 201);
+        assert!(response.body.starts_with(r#"{"id":1,"min":7,"max":7,"status":"playing""#), "{}", response.body);
+        assert_eq!(api.handle(&request("POST", "/games", "")).body.get(..7), Some(r#"{"id":2"#));
+    }
+
+    // Sends one request over a real connection and returns the status and the parsed body.
+    fn call(address: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, json::parse(body).unwrap())
+    }
+
+    fn number(value: &Value, key: &str) -> u32 {
+        match value.get(key) {
+            Some(Value::Number(n)) => n.parse().unwrap(),
+            other => panic!("{key} is {other:?}"),
+        }
+    }
+
+    #[test]
+    fn plays_a_game_over_tcp() {
+        // Port 0 lets the operating system pick a free port, so tests never fight over one.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, Api::new(1..=100), TIMEOUT));
+
+        let (status, game) = call(address, "POST", "/games", r#"{"min": 1, "max": 1000}"#);
+        assert_eq!(status, 201);
+        let id = number(&game, "id");
+
+        // Binary search, with the feedback coming back over HTTP.
+        let (mut low, mut high) = (1, 1000);
+        let attempts = loop {
+            let guess = (low + high) / 2;
+            let (status, answer) = call(address, "POST", &format!("/games/{id}/guesses"), &format!("{{\"guess\": {guess}}}"));
+            assert_eq!(status, 200);
+            match answer.get("result") {
+                Some(Value::String(r)) if r == "too small" => low = guess + 1,
+                Some(Value::String(r)) if r == "too big" => high = guess - 1,
+                _ => break number(&answer, "attempts"),
+            }
+        };
+        assert!(attempts <= 10);
+
+        let (status, game) = call(address, "GET", &format!("/games/{id}"), "");
+        assert_eq!(status, 200);
+        assert_eq!(game.get("status"), Some(&Value::String("won".to_string())));
+        assert_eq!(number(&game, "secret"), (low + high) / 2);
+
+        let (status, error) = call(address, "GET", "/games/999", "");
+        assert_eq!(status, 404);
+        assert_eq!(error.get("error"), Some(&Value::String("there is no game 999".to_string())));
+    }
+
+    #[test]
+    fn drops_idle_clients() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, Api::new(1..=100), Duration::from_millis(100)));
+
+        // This client connects and then says nothing, and one that sends half a request stalls as well. The next client is still answered once they time out.
+        let _silent = TcpStream::connect(address).unwrap();
+        let mut stalled = TcpStream::connect(address).unwrap();
+        write!(stalled, "POST /games HTTP/1.1\r\nContent-Length: 10\r\n\r\n{{").unwrap();
+        let (status, _) = call(address, "POST", "/games", "");
+        assert_eq!(status, 201);
+    }
+
+    #[test]
+    fn drops_slow_clients() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, Api::new(1..=100), Duration::from_millis(200)));
+
+        // One byte every 20ms never leaves the connection idle for long, but the whole request would take a minute.
+        let mut trickle = TcpStream::connect(address).unwrap();
+        thread::spawn(move || {
+            for byte in "POST /games HTTP/1.1\r\n".bytes().cycle().take(3000) {
+                if trickle.write_all(&[byte]).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(20));
+            }
+        });
+        thread::sleep(Duration::from_millis(50));
+
+        let start = Instant::now();
+        let (status, _) = call(address, "POST", "/games", "");
+        assert_eq!(status, 201);
+        assert!(start.elapsed() < Duration::from_secs(5), "{:?}", start.elapsed());
+    }
+
+    #[test]
+    fn refuses_oversized_headers() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, Api::new(1..=100), Duration::from_secs(5)));
+
+        // The header line stops exactly at the limit. Anything the server leaves unread when it closes the connection would make it reset the connection instead of letting the client read the response.
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET /games/1 HTTP/1.1\r\nCookie: {}", "x".repeat(crate::http::MAX_LINE - "Cookie: ".len())).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"), "{response}");
+    }
+}
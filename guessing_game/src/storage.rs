@@ -0,0 +1,39 @@
+// Everything the game saves lives in one directory: $GUESSING_GAME_DIR if it is set, otherwise .guessing_game in the home directory.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+pub fn path(file_name: &str) -> PathBuf {
+    let dir = match env::var_os("GUESSING_GAME_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => env::var_os("HOME").map_or(PathBuf::from("."), PathBuf::from).join(".guessing_game"),
+    };
+    dir.join(file_name)
+}
+
+// Reads a whole file, treating a file that does not exist yet as empty.
+pub fn read(file_name: &str) -> io::Result<String> {
+    match fs::read_to_string(path(file_name)) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        result => result,
+    }
+}
+
+pub fn write(file_name: &str, contents: &str) -> io::Result<()> {
+    let path = path(file_name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+// Adds to the end of a file without reading it first. Files that are only ever appended to cannot lose older lines, even if two games end at the same time.
+pub fn append(file_name: &str, contents: &str) -> io::Result<()> {
+    let path = path(file_name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::OpenOptions::new().create(true).append(true).open(path)?.write_all(contents.as_bytes())
+}
@@ -0,0 +1,117 @@
+// Keeps track of time during a game: how long each guess took, and for time attack how much of the countdown is left.
+
+use std::time::{Duration, Instant};
+
+// The timer asks a Clock for the current time instead of calling Instant::now directly. The game uses the real clock, tests use a FakeClock that only moves when told to.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+// Cell allows changing the time through a shared reference, which is all that Clock::now gets. This is called interior mutability.
+#[cfg(test)]
+pub struct FakeClock {
+    now: std::cell::Cell<Instant>,
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub fn new() -> FakeClock {
+        FakeClock { now: std::cell::Cell::new(Instant::now()) }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+// Any reference to a clock is a clock as well, so a test can keep its FakeClock and still hand the timer a &FakeClock.
+impl<C: Clock> Clock for &C {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+pub struct Timer<C: Clock> {
+    clock: C,
+    start: Instant,
+    last_lap: Instant,
+    limit: Option<Duration>,
+}
+
+impl<C: Clock> Timer<C> {
+    pub fn start(clock: C, limit: Option<Duration>) -> Timer<C> {
+        let now = clock.now();
+        Timer { clock, start: now, last_lap: now, limit }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now() - self.start
+    }
+
+    // None when there is no countdown at all. saturating_sub stops at zero rather than panicking once the time is over.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.limit.map(|limit| limit.saturating_sub(self.elapsed()))
+    }
+
+    pub fn expired(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+
+    // Returns the time since the previous guess, or since the start for the first one.
+    pub fn lap(&mut self) -> Duration {
+        let now = self.clock.now();
+        let lap = now - self.last_lap;
+        self.last_lap = now;
+        lap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_time_per_guess() {
+        let clock = FakeClock::new();
+        let mut timer = Timer::start(&clock, None);
+
+        clock.advance(Duration::from_secs(3));
+        assert_eq!(timer.lap(), Duration::from_secs(3));
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(timer.lap(), Duration::from_millis(1500));
+
+        assert_eq!(timer.elapsed(), Duration::from_millis(4500));
+        assert_eq!(timer.remaining(), None);
+        assert!(!timer.expired());
+    }
+
+    #[test]
+    fn counts_down_to_zero() {
+        let clock = FakeClock::new();
+        let timer = Timer::start(&clock, Some(Duration::from_secs(10)));
+
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(timer.remaining(), Some(Duration::from_secs(6)));
+        assert!(!timer.expired());
+
+        clock.advance(Duration::from_secs(6));
+        assert!(timer.expired());
+        clock.advance(Duration::from_secs(6));
+        assert_eq!(timer.remaining(), Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,350 @@
+// A full-screen front end for the same game. Everything is drawn with ANSI escape sequences, the codes that start with ESC [ and tell the terminal to move the cursor, clear the screen or change colours. Input is still read a line at a time, so the terminal never has to leave its normal line mode.
+// The game itself is the same State<T> from turn.rs the text mode uses: same parsing, same redundancy check, same answers, the same --explain and --analyze notes and the same GameRecord at the end.
+
+use std::cmp::Ordering;
+use std::io::{self, IsTerminal, Write};
+use std::ops::RangeInclusive;
+use std::panic;
+use std::sync::Arc;
+use std::thread;
+
+use crate::game::{Game, Guessable};
+use crate::interval::Interval;
+use crate::options::Options;
+use crate::record::GameRecord;
+use crate::timer::SystemClock;
+use crate::turn::{self, Line, State};
+
+const WIDTH: usize = 60;
+const HISTORY_ROWS: usize = 8;
+
+// The alternate screen is a second buffer that full-screen programs like less and vim draw on. Leaving it brings back whatever was in the terminal before.
+const ENTER: &str = "\x1b[?1049h\x1b[2J\x1b[H";
+const LEAVE: &str = "\x1b[0m\x1b[?25h\x1b[?1049l";
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const INVERSE: &str = "\x1b[7m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+// The full-screen mode only makes sense when both ends are a terminal. Piped output gets the text mode instead.
+pub fn available() -> bool {
+    io::stdout().is_terminal() && io::stdin().is_terminal()
+}
+
+// Puts the terminal back the way it was. Writing LEAVE twice does no harm, so both the guard and the panic hook can call this.
+fn restore() {
+    let mut stdout = io::stdout();
+    let _ = write!(stdout, "{LEAVE}");
+    let _ = stdout.flush();
+}
+
+// Ctrl-C makes the terminal send SIGINT, which ends the program on the spot: no Drop runs and no panic hook either, so the terminal would stay on the alternate screen. std has no API for signals, so the two functions needed from the C library that std already links are declared by hand.
+#[cfg(unix)]
+mod interrupt {
+    use std::ffi::c_int;
+
+    const SIGINT: c_int = 2;
+
+    unsafe extern "C" {
+        // Returns the handler that was installed before. Handlers are passed as plain addresses, like sighandler_t in C.
+        fn signal(signum: c_int, handler: usize) -> usize;
+        fn write(fd: c_int, buf: *const u8, count: usize) -> isize;
+        fn _exit(status: c_int) -> !;
+    }
+
+    // A signal handler may interrupt the program anywhere, even while stdout is locked, so it cannot use println! or the Stdout lock. write and _exit are safe to call from it. 130 is the exit code shells use for a program ended by Ctrl-C.
+    extern "C" fn leave(_: c_int) {
+        // SAFETY: write only reads the bytes of the constant, and _exit ends the process without running anything else.
+        unsafe {
+            write(1, super::LEAVE.as_ptr(), super::LEAVE.len());
+            _exit(130);
+        }
+    }
+
+    pub fn catch() -> usize {
+        // SAFETY: leave only calls functions that are safe in a signal handler.
+        unsafe { signal(SIGINT, leave as extern "C" fn(c_int) as usize) }
+    }
+
+    pub fn release(previous: usize) {
+        // SAFETY: previous came from signal, so it is a handler that was installed before.
+        unsafe {
+            signal(SIGINT, previous);
+        }
+    }
+}
+
+type Hook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+// Holds the terminal in full-screen mode. Drop runs whenever the guard goes out of scope, on a normal return as well as on an early one, and puts back the terminal, the panic hook and the Ctrl-C handler.
+struct Screen {
+    // The panic hook from before, shared with the hook Screen installs, which calls it after leaving the screen.
+    previous_hook: Option<Arc<Hook>>,
+    #[cfg(unix)]
+    previous_handler: usize,
+}
+
+impl Screen {
+    fn enter() -> Screen {
+        // A panic would print its message on the alternate screen, where it vanishes when the screen is left. The hook leaves the screen first and then lets the previous hook print the message.
+        let previous_hook = Arc::new(panic::take_hook());
+        let chained = Arc::clone(&previous_hook);
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            chained(info);
+        }));
+        let screen = Screen {
+            previous_hook: Some(previous_hook),
+            #[cfg(unix)]
+            previous_handler: interrupt::catch(),
+        };
+        print!("{ENTER}");
+        screen
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        restore();
+        #[cfg(unix)]
+        interrupt::release(self.previous_handler);
+        // The hook cannot be changed while a panic unwinds through here. The program is on its way out then anyway.
+        if thread::panicking() {
+            return;
+        }
+        // Taking out our hook drops its copy of the previous one, so try_unwrap can usually hand back the original box.
+        drop(panic::take_hook());
+        if let Some(previous) = self.previous_hook.take() {
+            match Arc::try_unwrap(previous) {
+                Ok(hook) => panic::set_hook(hook),
+                Err(shared) => panic::set_hook(Box::new(move |info| shared(info))),
+            }
+        }
+    }
+}
+
+// How many guesses binary search needs at most to find the secret in what is left. For floats every guess covers a window of twice the tolerance, and without a tolerance there is no bound.
+pub fn guesses_left<T: Guessable>(feasible: &Interval<T>, tolerance: f64) -> Option<u32> {
+    let values = if T::CONTINUOUS {
+        if tolerance <= 0.0 {
+            return None;
+        }
+        (feasible.size() / (2.0 * tolerance)).ceil()
+    } else {
+        feasible.size()
+    };
+    Some((values + 1.0).log2().ceil() as u32)
+}
+
+// The number line from the start to the end of the range. Values that are still possible are drawn solid, the ones that have been ruled out are shaded.
+pub fn gauge<T: Guessable>(range: &RangeInclusive<T>, feasible: &Interval<T>) -> String {
+    let (start, end) = (range.start().position(), range.end().position());
+    let span = (end - start).max(f64::MIN_POSITIVE);
+    // Which cell a position falls into, from 0 to WIDTH - 1.
+    let cell = |position: f64| (((position - start) / span) * (WIDTH - 1) as f64).round() as usize;
+
+    let (low, high) = (cell(feasible.low().position()), cell(feasible.high().position()));
+    (0..WIDTH).map(|i| if feasible.size() > 0.0 && (low..=high).contains(&i) { '█' } else { '░' }).collect()
+}
+
+struct View<'a, T: Guessable> {
+    game: &'a Game<T>,
+    history: &'a [(T, Ordering)],
+    message: &'a str,
+    // The --explain and --analyze notes of the last turn.
+    notes: &'a [String],
+    remaining: Option<u64>,
+}
+
+impl<T: Guessable> View<'_, T> {
+    fn render(&self) -> String {
+        let game = self.game;
+        let range = game.range();
+        let mut screen = String::from(CLEAR);
+        let mut line = |text: String| {
+            screen += &text;
+            screen += "\n";
+        };
+
+        line(format!("{BOLD} Guess the {} between {} and {}{RESET}", T::NAME, range.start(), range.end()));
+        line(String::new());
+        line(format!(" {:>8} {} {}", range.start().to_string(), gauge(range, game.feasible()), range.end()));
+        line(format!(" {:>8} {}", "", game.feasible()));
+        line(String::new());
+
+        line(format!("{BOLD} History{RESET}"));
+        let skipped = self.history.len().saturating_sub(HISTORY_ROWS);
+        if skipped > 0 {
+            line(format!("{DIM}   ... {skipped} earlier guesses{RESET}"));
+        }
+        for (i, (guess, ordering)) in self.history.iter().enumerate().skip(skipped) {
+            let feedback = match ordering {
+                Ordering::Less => "too small",
+                Ordering::Greater => "too big",
+                Ordering::Equal => "correct",
+            };
+            line(format!("  {:>3}. {:<12} {feedback}", i + 1, guess.to_string()));
+        }
+        for _ in self.history.len().min(HISTORY_ROWS)..HISTORY_ROWS {
+            line(String::new());
+        }
+        line(String::new());
+
+        let left = match guesses_left(game.feasible(), game.tolerance()) {
+            Some(n) => format!("at most {n} more with binary search"),
+            None => "no limit, the tolerance is 0".to_string(),
+        };
+        line(format!(" Attempts: {}   Remaining: {left}", game.attempts()));
+        line(String::new());
+        for note in self.notes {
+            line(format!("{DIM}{note}{RESET}"));
+        }
+        if !self.notes.is_empty() {
+            line(String::new());
+        }
+
+        // The status bar: the last message on the left, the clock on the right, in inverse video across the whole width.
+        let clock = match self.remaining {
+            Some(seconds) => format!("{seconds}s left "),
+            None => "q to quit ".to_string(),
+        };
+        let width = WIDTH + 18;
+        // Long messages are cut off, so the bar stays on one line.
+        let message: String = format!(" {}", self.message).chars().take(width - clock.chars().count() - 1).collect();
+        let padding = width.saturating_sub(message.chars().count() + clock.chars().count());
+        line(format!("{INVERSE}{message}{}{clock}{RESET}", " ".repeat(padding)));
+        screen += " > ";
+        screen
+    }
+}
+
+fn draw(text: &str) {
+    let mut stdout = io::stdout();
+    let _ = write!(stdout, "{text}");
+    let _ = stdout.flush();
+}
+
+// Sorts the lines of a turn into the status bar and the notes panel. The status bar has room for one line, so multi-line feedback like the caret under an invalid guess only keeps its first line.
+fn split(lines: &[Line]) -> (String, Vec<String>) {
+    let mut feedback = Vec::new();
+    let mut notes = Vec::new();
+    for line in lines {
+        match line {
+            Line::Feedback(text) => feedback.push(text.lines().next().unwrap_or_default()),
+            Line::Note(text) => notes.extend(text.lines().map(str::to_string)),
+        }
+    }
+    (feedback.join(" "), notes)
+}
+
+pub fn play<T: Guessable>(options: &Options) -> GameRecord {
+    let (range, secret) = crate::pick_secret::<T>(options);
+    let screen = Screen::enter();
+
+    let mut state = State::start(secret, range, options, SystemClock);
+    let mut message = String::from("Enter a guess.");
+    let mut notes = Vec::new();
+
+    let last = loop {
+        // The prompt of the text mode is left out, the status bar already asks for a guess. Its notes are kept.
+        let (_, prompt_notes) = split(&state.prompt());
+        notes.extend(prompt_notes);
+        let remaining = state.timer.remaining().map(|left| left.as_secs_f64().ceil() as u64);
+        draw(&View { game: &state.game, history: &state.history, message: &message, notes: &notes, remaining }.render());
+
+        let mut line = String::new();
+        io::stdin().read_line(&mut line).expect("Failed to read line");
+        // q quits, the same as the end of the input does.
+        if line.trim() == "q" {
+            line.clear();
+        }
+
+        let turn = state.turn(&line);
+        if turn.outcome.is_some() {
+            break turn;
+        }
+        (message, notes) = split(&turn.lines);
+    };
+
+    // Leave the full screen before printing, so the last turn and the summary stay in the terminal afterwards.
+    drop(screen);
+    turn::print(&last.lines);
+    let (lines, record) = state.finish(last.outcome.expect("the loop only ends with an outcome"));
+    turn::print(&lines);
+    println!("{record}");
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_the_guesses_binary_search_needs() {
+        assert_eq!(guesses_left(&Interval::new(&(1u32..=100)), 0.0), Some(7));
+        assert_eq!(guesses_left(&Interval::new(&(1u32..=127)), 0.0), Some(7));
+        assert_eq!(guesses_left(&Interval::new(&(5u32..=5)), 0.0), Some(1));
+        assert_eq!(guesses_left(&Interval::new(&(0.0..=100.0)), 0.5), Some(7));
+        assert_eq!(guesses_left(&Interval::new(&(0.0..=100.0)), 0.0), None);
+    }
+
+    #[test]
+    fn draws_the_feasible_part_of_the_range() {
+        let range = 1u32..=100;
+        let mut feasible = Interval::new(&range);
+        assert_eq!(gauge(&range, &feasible), "█".repeat(WIDTH));
+
+        feasible.narrow(50, Ordering::Less);
+        let gauge = gauge(&range, &feasible);
+        assert_eq!(gauge.chars().count(), WIDTH);
+        assert_eq!(gauge.chars().filter(|&c| c == '█').count(), WIDTH / 2);
+        assert!(gauge.starts_with('░') && gauge.ends_with('█'));
+    }
+
+    #[test]
+    fn renders_the_history_and_status_bar() {
+        let mut game = Game::new(42u32, 1..=100, 0.0);
+        game.guess(50);
+        let history = [(50, Ordering::Greater)];
+        let screen = View { game: &game, history: &history, message: "Too big!", notes: &[], remaining: Some(12) }.render();
+
+        assert!(screen.contains("    1. 50           too big"));
+        assert!(screen.contains("between 1 and 49"));
+        assert!(screen.contains("Attempts: 1   Remaining: at most 6 more"));
+        assert!(screen.contains(&format!("{INVERSE} Too big!")));
+        assert!(screen.contains("12s left "));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn puts_back_the_interrupt_handler() {
+        // catch hands back whatever handler was installed, so catching twice around the screen shows what the screen left behind.
+        let before = interrupt::catch();
+        interrupt::release(before);
+        drop(Screen::enter());
+        let after = interrupt::catch();
+        interrupt::release(after);
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn explains_turns_in_the_notes_panel() {
+        let options = Options::parse(["--tui", "--explain", "--analyze"].iter().map(|arg| arg.to_string())).unwrap();
+        let mut state = State::start(42u32, 1..=100, &options, SystemClock);
+        let (_, mut notes) = split(&state.prompt());
+        assert!(notes[1].starts_with("  [loop]"));
+
+        let (message, turn_notes) = split(&state.turn("50\n").lines);
+        notes.extend(turn_notes);
+        assert_eq!(message, "You guessed: 50 Too big!");
+        let screen = View { game: &state.game, history: &state.history, message: &message, notes: &notes, remaining: None }.render();
+        assert!(screen.contains(&format!("{DIM}  [references] `read_line(&mut guess)` appended 3 bytes")));
+        assert!(screen.contains("[match arms] the `Ordering::Greater` arm of the match ran."));
+        assert!(screen.contains(&format!("{DIM}Analysis: gained ")));
+        assert!(screen.contains(&format!("{INVERSE} You guessed: 50 Too big!")));
+
+        let (message, _) = split(&state.turn("abc\n").lines);
+        assert!(message.starts_with("Invalid guess: ") && !message.contains('\n'));
+    }
+}
@@ -0,0 +1,282 @@
+// What happens between reading a line and asking for the next one. The text mode in main.rs and the full-screen mode in tui.rs only differ in how they read input and show output, so both hand every line they read to State::turn and show the lines that come back. That way --explain, --analyze, the observers and the events behave the same in both.
+
+use std::cmp::Ordering;
+use std::mem;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::analysis;
+use crate::concepts::{self, Concept};
+use crate::distribution::Distribution;
+use crate::game::{Game, Guessable, Redundant};
+use crate::observer::{GameObserver, Observers, Stamp};
+use crate::options::Options;
+use crate::record::{Event, GameRecord, GuessRecord, Outcome};
+use crate::timer::{Clock, Timer};
+
+// One line of output. The text mode prints both kinds in order, the full-screen mode shows feedback in its status bar and notes in a panel of their own.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    // What the game tells the player, e.g. "Too small!".
+    Feedback(String),
+    // An --explain note or an --analyze line.
+    Note(String),
+}
+
+impl Line {
+    pub fn text(&self) -> &str {
+        match self {
+            Line::Feedback(text) | Line::Note(text) => text,
+        }
+    }
+}
+
+pub fn print(lines: &[Line]) {
+    for line in lines {
+        println!("{}", line.text());
+    }
+}
+
+pub struct Turn {
+    pub lines: Vec<Line>,
+    // Some once the game is over.
+    pub outcome: Option<Outcome>,
+}
+
+pub struct State<T: Guessable, C: Clock> {
+    pub game: Game<T>,
+    pub timer: Timer<C>,
+    pub history: Vec<(T, Ordering)>,
+    guesses: Vec<GuessRecord>,
+    events: Vec<Event>,
+    observers: Observers,
+    explain: bool,
+    analyze: bool,
+    free_redundant: bool,
+    time_limit: Option<Duration>,
+    iteration: u32,
+    // The output of the current step, handed out by prompt and turn.
+    lines: Vec<Line>,
+}
+
+impl<T: Guessable, C: Clock> State<T, C> {
+    pub fn start(secret: T, range: RangeInclusive<T>, options: &Options, clock: C) -> State<T, C> {
+        let text = format!("{}..={}", range.start(), range.end());
+        let mut state = State {
+            game: Game::new(secret, range, options.tolerance),
+            timer: Timer::start(clock, options.time_limit),
+            history: Vec::new(),
+            guesses: Vec::new(),
+            events: vec![Event::Started { range: text.clone() }],
+            observers: Observers::default(),
+            explain: options.explain,
+            analyze: options.analyze,
+            free_redundant: options.free_redundant,
+            time_limit: options.time_limit,
+            iteration: 0,
+            lines: Vec::new(),
+        };
+        for builtin in &options.observers {
+            state.observers.register(builtin.create());
+        }
+        state.observers.game_started(&text, Stamp::now(state.timer.elapsed(), 0));
+        let distribution = options.distribution.unwrap_or(Distribution::Uniform);
+        state.note(Concept::Rng, &[("sampler", distribution.sampler()), ("range", &text), ("distribution", &distribution.to_string())]);
+        state
+    }
+
+    fn note(&mut self, concept: Concept, values: &[(&str, &str)]) {
+        if self.explain {
+            self.lines.push(Line::Note(concepts::describe(concept, values)));
+        }
+    }
+
+    fn say(&mut self, text: String) {
+        self.lines.push(Line::Feedback(text));
+    }
+
+    fn stamp(&self) -> Stamp {
+        Stamp::now(self.timer.elapsed(), self.game.attempts())
+    }
+
+    // The start of an iteration of the loop, before a line is read.
+    pub fn prompt(&mut self) -> Vec<Line> {
+        self.iteration += 1;
+        self.note(Concept::Loop, &[("iteration", &self.iteration.to_string())]);
+        let prompt = match self.timer.remaining() {
+            Some(remaining) => format!("Please input your guess ({}, {}s left).", self.game.feasible(), remaining.as_secs_f64().ceil()),
+            None => format!("Please input your guess ({}).", self.game.feasible()),
+        };
+        self.say(prompt);
+        mem::take(&mut self.lines)
+    }
+
+    // line is what read_line appended, newline included. An empty line means the input has ended.
+    pub fn turn(&mut self, line: &str) -> Turn {
+        let outcome = self.guess(line);
+        if let Some(outcome) = outcome {
+            self.note(Concept::Break, &[("outcome", &format!("Outcome::{outcome:?}"))]);
+        }
+        Turn { lines: mem::take(&mut self.lines), outcome }
+    }
+
+    fn guess(&mut self, line: &str) -> Option<Outcome> {
+        let bytes = line.len().to_string();
+        self.note(Concept::MutableString, &[]);
+        self.note(Concept::ReadLine, &[("bytes", &bytes)]);
+        self.note(Concept::Expect, &[("bytes", &bytes)]);
+
+        if line.is_empty() { // read_line only leaves the string empty at the end of the input, e.g. after Ctrl-D. Without this check the loop would spin forever.
+            self.say(format!("The {} was {}.", T::NAME, self.game.secret()));
+            return Some(Outcome::GaveUp);
+        }
+
+        // The countdown is only checked between guesses. A guess typed after the time ran out does not count.
+        if self.timer.expired() {
+            self.say(format!("Time's up! The {} was {}.", T::NAME, self.game.secret()));
+            return Some(Outcome::OutOfTime);
+        }
+
+        let input = line.trim();
+        self.note(Concept::Trim, &[("input", input)]);
+
+        let guess: T = match T::parse_guess(input) {
+            Ok(num) => {
+                self.note(Concept::ParseOk, &[("value", &num.to_string())]);
+                num
+            }
+            Err(error) => {
+                self.say(format!("Invalid guess: {}", error.render(input)));
+                self.note(Concept::ParseErr, &[]);
+                self.events.push(Event::Invalid { input: input.to_string() });
+                self.observers.invalid_input(input, self.stamp());
+                return None; // Returning None ends the turn without an outcome, and the loop of the front end goes on to ask for another guess, as continue would.
+            }
+        }; // Rust allows us to shadow the previous value of guess with a new one. Shadowing lets us reuse the guess variable name rather than forcing us to create two unique variables, such as guess_str and guess.
+        self.note(Concept::Shadowing, &[("type", std::any::type_name::<T>())]);
+
+        // {} is a placeholder. If you want to print the result of an expression, you can do this: println!("x = {x} and y + 2 = {}", y + 2);
+        if input == guess.to_string() {
+            self.say(format!("You guessed: {guess}"));
+        } else {
+            self.say(format!("You guessed: {guess} (from {input})"));
+        }
+
+        // if let runs the block only when the pattern matches, here when the guess is redundant.
+        if let Some(reason) = self.game.redundancy(guess) {
+            self.events.push(Event::Redundant { guess: guess.to_string(), reason });
+            match reason {
+                Redundant::Repeated => self.say(format!("You already guessed {guess}.")),
+                Redundant::Infeasible => self.say(format!("That cannot be it, the {} is {}.", T::NAME, self.game.feasible())),
+            }
+            if self.free_redundant {
+                self.say("This guess does not count.".to_string());
+                return None;
+            }
+        }
+
+        let feasible_before = *self.game.feasible();
+        let Some(ordering) = self.game.guess(guess) else {
+            self.say(format!("Please guess between {} and {}.", self.game.range().start(), self.game.range().end()));
+            return None;
+        };
+        self.guesses.push(GuessRecord { guess: guess.to_string(), ordering, time: self.timer.lap() });
+        self.history.push((guess, ordering));
+        self.observers.guess_made(&guess.to_string(), ordering, self.stamp());
+
+        let step = analysis::step(&feasible_before, self.game.tolerance(), guess, ordering);
+        self.events.push(Event::Guessed { guess: guess.to_string(), ordering, optimal: step.expected >= step.best_expected - 1e-9 });
+
+        let method = if T::CONTINUOUS { "partial_cmp (with a tolerance)" } else { "cmp" };
+        let variant = format!("{ordering:?}");
+        self.note(Concept::Cmp, &[("method", method), ("ordering", &variant)]);
+
+        // A match expression is made up of arms. An arm consists of a pattern to match against, and the code that should be run if the value given to match fits that arm’s pattern.
+        match ordering {
+            Ordering::Less => self.say("Too small!".to_string()),
+            Ordering::Greater => self.say("Too big!".to_string()),
+            Ordering::Equal => {
+                self.say(format!("You win after {} guesses!", self.game.attempts()));
+                if guess != self.game.secret() { // Only happens for floats that were within the tolerance.
+                    self.say(format!("The {} was {}.", T::NAME, self.game.secret()));
+                }
+            },
+        }
+
+        self.note(Concept::MatchArm, &[("ordering", &variant)]);
+
+        if self.analyze {
+            self.lines.push(Line::Note(step.to_string()));
+        }
+
+        (ordering == Ordering::Equal).then_some(Outcome::Won)
+    }
+
+    // Ends the game: the analysis of the whole game with --analyze, the observers, and the record that gets stored.
+    pub fn finish(mut self, outcome: Outcome) -> (Vec<Line>, GameRecord) {
+        if self.analyze {
+            let steps = analysis::analyze(self.game.range(), self.game.tolerance(), &self.history);
+            self.lines.push(Line::Note(analysis::Report::new(self.game.range(), self.game.tolerance(), &steps).to_string()));
+        }
+
+        let stamp = self.stamp();
+        match outcome {
+            Outcome::Won => self.observers.game_won(stamp),
+            lost => self.observers.game_lost(lost, stamp),
+        }
+
+        self.events.push(Event::Finished { outcome });
+        let record = GameRecord { outcome, guesses: self.guesses, total: self.timer.elapsed(), time_limit: self.time_limit, events: self.events };
+        (self.lines, record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::FakeClock;
+
+    fn options(args: &[&str]) -> Options {
+        Options::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    fn notes(lines: &[Line]) -> Vec<&str> {
+        lines.iter().filter_map(|line| if let Line::Note(text) = line { Some(text.as_str()) } else { None }).collect()
+    }
+
+    #[test]
+    fn answers_a_guess() {
+        let clock = FakeClock::new();
+        let mut state = State::start(42u32, 1..=100, &options(&[]), &clock);
+        assert_eq!(state.prompt(), [Line::Feedback("Please input your guess (between 1 and 100).".to_string())]);
+
+        let turn = state.turn("50\n");
+        assert_eq!(turn.lines, [Line::Feedback("You guessed: 50".to_string()), Line::Feedback("Too big!".to_string())]);
+        assert_eq!(turn.outcome, None);
+        assert_eq!(state.turn("42\n").outcome, Some(Outcome::Won));
+        assert_eq!(state.turn("").outcome, Some(Outcome::GaveUp));
+
+        let (lines, record) = state.finish(Outcome::Won);
+        assert!(lines.is_empty());
+        assert_eq!(record.attempts(), 2);
+        assert_eq!(record.events.last(), Some(&Event::Finished { outcome: Outcome::Won }));
+    }
+
+    #[test]
+    fn adds_notes_for_explain_and_analyze() {
+        let clock = FakeClock::new();
+        let mut state = State::start(42u32, 1..=100, &options(&["--explain", "--analyze"]), &clock);
+        let prompt = state.prompt();
+        assert!(notes(&prompt)[0].starts_with("  [traits in scope] `rand::thread_rng().gen_range(...)` picked the secret from 1..=100, with the uniform distribution."));
+        assert_eq!(notes(&prompt)[1], "  [loop] `loop` starts iteration 1. It only ends with `break` or `return`.");
+
+        let turn = state.turn("42\n");
+        let notes = notes(&turn.lines);
+        assert!(notes.contains(&"  [references] `read_line(&mut guess)` appended 3 bytes to the String it borrowed through a mutable reference."));
+        assert!(notes.iter().any(|note| note.starts_with("Analysis: gained")));
+        assert_eq!(notes.last(), Some(&"  [break] `break` ends the loop and hands Outcome::Won out of it as the value of the loop expression."));
+
+        let (lines, _) = state.finish(Outcome::Won);
+        assert!(lines[0].text().starts_with("Analysis of the game:"));
+    }
+}
@@ -7,11 +7,7 @@
 const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
 
 fn main() {
-    // Rust variables are by default immutable.
-    // let x = 5;
-    // println!("The value of x is: {x}");
-    // x = 6; // This will not compile -> ^^^^^ cannot assign twice to immutable variable.
-    // println!("The value of x is: {x}");
+    // Rust variables are by default immutable. Assigning to x a second time without mut will not compile -> cannot assign twice to immutable variable. The example is in tests/compile_fail/assign_twice.rs, and `cargo test` checks that it still fails.
 
     let mut x = 5; // This will work since x is explicitly declared as mutable.
     println!("The value of x is: {x}");
@@ -36,14 +32,13 @@ fn main() {
     // let spaces = "    ";
     // let spaces = spaces.len();
 
-    // If we use mut instead of shadowing, this will throw a compile error
-    // let mut spaces = "    ";
-    // spaces = spaces.len();
+    // If we use mut instead of shadowing, e.g. let mut spaces = "    "; spaces = spaces.len();, this will throw a compile error. See tests/compile_fail/mut_changes_type.rs.
 
-    let mut z: u32 = 1;
+    let z: u32 = 1;
     {
-        let mut z = z;
+        let mut z = z; // A shadowing variable can be mutable even if the one it shadows is not.
         z += 2;
+        println!("The value of z in the inner scope is: {z}"); // Will print 3
     }
-    println!("{z}");
+    println!("{z}"); // Will print 1
 }
@@ -0,0 +1,10 @@
+// lesson: variables
+// expected: error[E0384]
+// explanation: Rust variables are by default immutable, so x cannot be assigned a second time. Declaring it with `let mut x` would make this work.
+
+fn main() {
+    let x = 5;
+    println!("The value of x is: {x}");
+    x = 6;
+    println!("The value of x is: {x}");
+}
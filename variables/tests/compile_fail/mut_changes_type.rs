@@ -0,0 +1,9 @@
+// lesson: variables
+// expected: error[E0308]
+// explanation: mut lets a variable change its value, but never its type. spaces is a &str, so it cannot hold the usize that len() returns. Shadowing with a second `let spaces` creates a new variable, which may have a new type.
+
+fn main() {
+    let mut spaces = "    ";
+    spaces = spaces.len();
+    println!("{spaces}");
+}
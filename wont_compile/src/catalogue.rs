@@ -0,0 +1,100 @@
+// The catalogue is every snippet in the tests/compile_fail directory of every lesson. A snippet is a whole program that must not compile, and it starts with comments that describe it:
+//
+//   // lesson: variables
+//   // expected: error[E0384]
+//   // explanation: Rust variables are by default immutable, ...
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, PartialEq)]
+pub struct Snippet {
+    pub path: PathBuf,
+    pub lesson: String,
+    // Text that must appear in rustc's errors, usually an error code like error[E0308]. Some errors have no code, then it is the message.
+    pub expected: String,
+    pub explanation: String,
+}
+
+impl Snippet {
+    // Reads the metadata from the comments at the top of the source. Parsing stops at the first line that is not such a comment.
+    pub fn parse(path: &Path, source: &str) -> Result<Snippet, String> {
+        let (mut lesson, mut expected, mut explanation) = (None, None, None);
+        for line in source.lines() {
+            let Some((key, value)) = line.strip_prefix("// ").and_then(|comment| comment.split_once(": ")) else {
+                break;
+            };
+            let field = match key {
+                "lesson" => &mut lesson,
+                "expected" => &mut expected,
+                "explanation" => &mut explanation,
+                _ => return Err(format!("{}: unknown metadata `{key}`", path.display())),
+            };
+            *field = Some(value.trim().to_string());
+        }
+
+        // ok_or turns a missing value into an error, and ? returns it.
+        let missing = |key: &str| format!("{}: missing `// {key}:` line", path.display());
+        Ok(Snippet {
+            path: path.to_path_buf(),
+            lesson: lesson.ok_or_else(|| missing("lesson"))?,
+            expected: expected.ok_or_else(|| missing("expected"))?,
+            explanation: explanation.ok_or_else(|| missing("explanation"))?,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        let file = self.path.file_name().map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+        format!("{}/{file}", self.lesson)
+    }
+}
+
+// Finds the snippets of every lesson below root, sorted by lesson and file name so the report is always in the same order.
+pub fn load(root: &Path) -> io::Result<Vec<Result<Snippet, String>>> {
+    let mut paths = Vec::new();
+    for lesson in fs::read_dir(root)? {
+        let dir = lesson?.path().join("tests").join("compile_fail");
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "rs") {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+
+    let mut snippets = Vec::new();
+    for path in paths {
+        let source = fs::read_to_string(&path)?;
+        snippets.push(Snippet::parse(&path, &source));
+    }
+    Ok(snippets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_metadata() {
+        let source = "// lesson: functions\n// expected: error[E0308]\n// explanation: The semicolon makes it a statement.\n\nfn main() {}\n";
+        let snippet = Snippet::parse(Path::new("functions/tests/compile_fail/plus_one.rs"), source).unwrap();
+        assert_eq!(snippet.lesson, "functions");
+        assert_eq!(snippet.expected, "error[E0308]");
+        assert_eq!(snippet.explanation, "The semicolon makes it a statement.");
+        assert_eq!(snippet.name(), "functions/plus_one.rs");
+    }
+
+    #[test]
+    fn rejects_incomplete_metadata() {
+        let path = Path::new("a.rs");
+        assert_eq!(Snippet::parse(path, "// lesson: x\nfn main() {}"), Err("a.rs: missing `// expected:` line".to_string()));
+        assert_eq!(Snippet::parse(path, "// lessons: x\n"), Err("a.rs: unknown metadata `lessons`".to_string()));
+        // Metadata after the code does not count.
+        assert!(Snippet::parse(path, "fn main() {}\n// lesson: x\n// expected: y\n// explanation: z\n").is_err());
+    }
+}
@@ -0,0 +1,78 @@
+// Running rustc on a snippet and judging the result. The wont_compile program uses this for the whole catalogue, and the lessons use it from their own tests/compile_fail.rs, so there is one place that knows how to ask the compiler.
+
+pub mod catalogue;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use catalogue::Snippet;
+
+// The edition all lessons use, see their Cargo.toml.
+const EDITION: &str = "2024";
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    // rustc rejected the snippet with the expected error.
+    Confirmed,
+    // rustc rejected it, but for another reason. Holds the errors it reported.
+    Changed(Vec<String>),
+    Compiles,
+}
+
+// out_dir receives the metadata file rustc writes for snippets that do compile. Err means rustc could not be started at all.
+pub fn check(snippet: &Snippet, out_dir: &Path) -> io::Result<Verdict> {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    // --emit=metadata stops after type checking, and a short error format gives one line per error.
+    let output = Command::new(&rustc)
+        .args(["--edition", EDITION, "--error-format=short", "--emit=metadata", "--crate-name", "snippet"])
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(&snippet.path)
+        .output()?;
+
+    if output.status.success() {
+        return Ok(Verdict::Compiles);
+    }
+    let errors = String::from_utf8_lossy(&output.stderr);
+    if errors.contains(&snippet.expected) {
+        Ok(Verdict::Confirmed)
+    } else {
+        // "error: aborting due to ..." is only the summary, the real errors are the other lines that mention error.
+        Ok(Verdict::Changed(errors.lines().filter(|line| line.contains("error") && !line.starts_with("error: aborting")).map(String::from).collect()))
+    }
+}
+
+// For the tests of a lesson: panics unless the snippet at path still fails the way its metadata says.
+pub fn assert_fails(path: &Path, out_dir: &Path) {
+    let source = fs::read_to_string(path).unwrap_or_else(|error| panic!("Could not read {}: {error}", path.display()));
+    let snippet = Snippet::parse(path, &source).unwrap_or_else(|message| panic!("{message}"));
+    match check(&snippet, out_dir).expect("rustc could not be started") {
+        Verdict::Confirmed => {}
+        Verdict::Changed(errors) => panic!("{} should fail with `{}`, but rustc said:\n{}", snippet.name(), snippet.expected, errors.join("\n")),
+        Verdict::Compiles => panic!("{} compiled, but the lesson says it does not", snippet.name()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn snippet(name: &str, source: &str, expected: &str) -> Snippet {
+        let path = env::temp_dir().join(format!("wont_compile_test_{name}.rs"));
+        fs::write(&path, source).unwrap();
+        Snippet { path, lesson: "test".to_string(), expected: expected.to_string(), explanation: String::new() }
+    }
+
+    #[test]
+    fn judges_what_rustc_says() {
+        let out_dir: PathBuf = env::temp_dir().join("wont_compile_test");
+        let assign = "fn main() { let x = 5; x = 6; println!(\"{x}\"); }";
+        assert_eq!(check(&snippet("confirmed", assign, "error[E0384]"), &out_dir).unwrap(), Verdict::Confirmed);
+        assert!(matches!(check(&snippet("changed", assign, "error[E0308]"), &out_dir).unwrap(), Verdict::Changed(errors) if errors[0].contains("E0384")));
+        assert_eq!(check(&snippet("compiles", "fn main() {}", "error[E0308]"), &out_dir).unwrap(), Verdict::Compiles);
+    }
+}
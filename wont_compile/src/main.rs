@@ -0,0 +1,80 @@
+// The lessons make claims about code the compiler rejects. This program checks those claims: it runs the local rustc on every snippet in the catalogue and reports the ones that now compile, or that fail with a different error than the lesson says.
+//
+//   cargo run              checks every snippet, exits with 1 if any claim no longer holds
+//   cargo run -- --list    prints the catalogue with the explanations
+//   cargo run -- DIR       looks for lessons in DIR instead of the repository root
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use wont_compile::{Verdict, catalogue};
+
+fn main() {
+    let mut list = false;
+    // The repository root is the parent of this crate's directory.
+    let mut root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("the crate lives inside the repository").to_path_buf();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--list" => list = true,
+            dir if !dir.starts_with("--") => root = PathBuf::from(dir),
+            other => {
+                eprintln!("unexpected argument `{other}`\nusage: wont_compile [--list] [DIR]");
+                process::exit(2);
+            }
+        }
+    }
+
+    let snippets = catalogue::load(&root).unwrap_or_else(|error| {
+        eprintln!("Could not read the lessons in {}: {error}", root.display());
+        process::exit(2);
+    });
+    if snippets.is_empty() {
+        eprintln!("No snippets found in {}/*/tests/compile_fail.", root.display());
+        process::exit(2);
+    }
+
+    let out_dir = env::temp_dir().join("wont_compile");
+    let mut problems = 0;
+    for snippet in &snippets {
+        let snippet = match snippet {
+            Ok(snippet) => snippet,
+            Err(message) => {
+                println!("BROKEN    {message}");
+                problems += 1;
+                continue;
+            }
+        };
+
+        if list {
+            println!("{}  ({})\n    {}\n", snippet.name(), snippet.expected, snippet.explanation);
+            continue;
+        }
+
+        let verdict = wont_compile::check(snippet, &out_dir).unwrap_or_else(|error| {
+            eprintln!("Could not run rustc: {error}");
+            process::exit(2);
+        });
+        match verdict {
+            Verdict::Confirmed => println!("ok        {:<40} {}", snippet.name(), snippet.expected),
+            Verdict::Changed(errors) => {
+                println!("CHANGED   {:<40} expected {}, rustc now says:", snippet.name(), snippet.expected);
+                for error in errors {
+                    println!("              {error}");
+                }
+                problems += 1;
+            }
+            Verdict::Compiles => {
+                println!("COMPILES  {:<40} expected {}, but it compiles now", snippet.name(), snippet.expected);
+                problems += 1;
+            }
+        }
+    }
+
+    if !list {
+        println!("\n{} snippets, {problems} no longer as the lessons describe them.", snippets.len());
+    }
+    if problems > 0 {
+        process::exit(1);
+    }
+}